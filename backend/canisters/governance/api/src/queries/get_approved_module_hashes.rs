@@ -0,0 +1,16 @@
+use crate::types::EmptyArgs;
+use candid::{CandidType, Principal};
+use serde::Deserialize;
+
+pub type Args = EmptyArgs;
+pub type Response = GetApprovedModuleHashesResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum GetApprovedModuleHashesResponse {
+    Ok(GetApprovedModuleHashesResult),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct GetApprovedModuleHashesResult {
+    pub module_hashes: Vec<(Principal, Vec<String>)>,
+}
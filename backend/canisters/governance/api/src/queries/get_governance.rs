@@ -1,4 +1,4 @@
-use crate::types::{EmptyArgs, Governance};
+use crate::types::{EmptyArgs, Governance, GovernanceVersion};
 use candid::CandidType;
 use serde::Deserialize;
 
@@ -14,4 +14,5 @@ pub enum GetGovernanceResponse {
 #[derive(CandidType, Deserialize, Debug)]
 pub struct GetGovernanceResult {
     pub governance: Governance,
+    pub governance_version: GovernanceVersion,
 }
@@ -0,0 +1,16 @@
+use crate::types::{EmptyArgs, GovernanceFreeze};
+use candid::CandidType;
+use serde::Deserialize;
+
+pub type Args = EmptyArgs;
+pub type Response = GetGovernanceFreezeResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum GetGovernanceFreezeResponse {
+    Ok(GetGovernanceFreezeResult),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct GetGovernanceFreezeResult {
+    pub freeze: Option<GovernanceFreeze>,
+}
@@ -0,0 +1,16 @@
+use crate::types::{EmptyArgs, ManagedCanisterStatus};
+use candid::CandidType;
+use serde::Deserialize;
+
+pub type Args = EmptyArgs;
+pub type Response = GetManagedCanisterStatusesResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum GetManagedCanisterStatusesResponse {
+    Ok(GetManagedCanisterStatusesResult),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct GetManagedCanisterStatusesResult {
+    pub statuses: Vec<ManagedCanisterStatus>,
+}
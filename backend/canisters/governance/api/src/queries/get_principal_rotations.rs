@@ -0,0 +1,17 @@
+use crate::types::{EmptyArgs, PrincipalRotation};
+use candid::CandidType;
+use serde::Deserialize;
+
+pub type Args = EmptyArgs;
+pub type Response = GetPrincipalRotationsResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum GetPrincipalRotationsResponse {
+    Ok(GetPrincipalRotationsResult),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct GetPrincipalRotationsResult {
+    pub pending: Vec<PrincipalRotation>,
+    pub completed: Vec<PrincipalRotation>,
+}
@@ -0,0 +1,39 @@
+use crate::types::{ProposalId, UpgradeSource};
+use candid::CandidType;
+use serde::Deserialize;
+
+pub type Args = GetUpgradeVerificationArgs;
+pub type Response = GetUpgradeVerificationResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct GetUpgradeVerificationArgs {
+    pub proposal_id: ProposalId,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum GetUpgradeVerificationResponse {
+    Ok(GetUpgradeVerificationResult),
+    Err(GetUpgradeVerificationError),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct GetUpgradeVerificationResult {
+    pub module_hash: String,
+    pub source: Option<UpgradeSource>,
+    pub uploaded_wasm: Option<UploadedWasmVerification>,
+}
+
+/// The wasm module uploaded by the operator for this upgrade, hashed by the uploader.
+#[derive(CandidType, Deserialize, Debug)]
+pub struct UploadedWasmVerification {
+    pub wasm_length: u64,
+    pub wasm_hash: String,
+    pub hash_matches: bool,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum GetUpgradeVerificationError {
+    ProposalNotFound,
+    NotUpgradeProposal,
+    CallError { reason: String },
+}
@@ -0,0 +1,16 @@
+use crate::types::{EmptyArgs, VoteDelegation};
+use candid::{CandidType, Principal};
+use serde::Deserialize;
+
+pub type Args = EmptyArgs;
+pub type Response = GetVoteDelegationsResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum GetVoteDelegationsResponse {
+    Ok(GetVoteDelegationsResult),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct GetVoteDelegationsResult {
+    pub delegations: Vec<(Principal, VoteDelegation)>,
+}
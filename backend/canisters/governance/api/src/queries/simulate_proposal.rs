@@ -0,0 +1,51 @@
+use crate::types::{GovernanceParticipant, PerformResult, ProposalId, ProposalType, VotingConfig};
+use candid::{CandidType, Principal};
+use serde::Deserialize;
+
+pub type Args = SimulateProposalArgs;
+pub type Response = SimulateProposalResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct SimulateProposalArgs {
+    pub proposal_id: ProposalId,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum SimulateProposalResponse {
+    Ok(SimulateProposalResult),
+    Err(SimulateProposalError),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum SimulateProposalResult {
+    CallResponse { result: PerformResult },
+    GovernanceDiff { diff: GovernanceDiff },
+}
+
+#[derive(CandidType, Deserialize, Debug, Default)]
+pub struct GovernanceDiff {
+    pub added_participants: Vec<(Principal, GovernanceParticipant)>,
+    pub removed_participants: Vec<Principal>,
+    pub changed_participants: Vec<ParticipantChange>,
+    pub voting_config_changes: Vec<VotingConfigChange>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct ParticipantChange {
+    pub participant: Principal,
+    pub old: GovernanceParticipant,
+    pub new: GovernanceParticipant,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct VotingConfigChange {
+    pub proposal_type: ProposalType,
+    pub old: Option<VotingConfig>,
+    pub new: Option<VotingConfig>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum SimulateProposalError {
+    ProposalNotFound,
+    NotSimulatable { reason: String },
+}
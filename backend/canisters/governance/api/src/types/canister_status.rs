@@ -0,0 +1,20 @@
+use crate::types::TimestampMillis;
+use candid::{CandidType, Nat, Principal};
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ManagedCanisterStatus {
+    pub canister_id: Principal,
+    pub name: String,
+    pub uploader_id: Principal,
+    pub status: Option<CanisterStatusSummary>,
+    pub error: Option<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CanisterStatusSummary {
+    pub module_hash: Option<String>,
+    pub cycles: Nat,
+    pub controllers: Vec<Principal>,
+    pub updated: TimestampMillis,
+}
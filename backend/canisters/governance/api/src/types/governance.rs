@@ -1,22 +1,147 @@
+use crate::types::TimestampMillis;
 use candid::{CandidType, Principal};
 use serde::{Deserialize, Serialize};
 
+pub type GovernanceVersion = u64;
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
 pub struct Governance {
     pub participants: Vec<(Principal, GovernanceParticipant)>,
     pub voting_configuration: Vec<(ProposalType, VotingConfig)>,
+    pub managed_canisters: Option<Vec<ManagedCanister>>,
+    pub cycles_top_up: Option<CyclesTopUpPolicy>,
+    pub stale_proposal_policy: Option<StaleProposalPolicy>,
+    pub roles: Option<Vec<GovernanceRole>>,
+    pub canister_groups: Option<Vec<CanisterGroup>>,
+    pub scoped_voting_configuration: Option<Vec<ScopedVotingConfig>>,
+    pub self_targeting_voting_config: Option<VotingConfig>,
+    pub call_policy: Option<CallPolicy>,
+    pub principal_rotation_delay_millis: Option<u64>,
+    pub guardians: Option<GuardianConfig>,
+}
+
+/// Guardians can freeze the governance for at most `max_freeze_duration_millis`,
+/// `unfreeze_quorum` of them are required to unfreeze it earlier.
+/// After a freeze ends, the next one is possible only after another `max_freeze_duration_millis`.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct GuardianConfig {
+    pub guardians: Vec<Principal>,
+    pub max_freeze_duration_millis: u64,
+    pub unfreeze_quorum: u32,
+}
+
+/// While active, new proposals can not be added and approved proposals can not be performed.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct GovernanceFreeze {
+    pub guardian: Principal,
+    pub reason: Option<String>,
+    pub started: TimestampMillis,
+    pub until: TimestampMillis,
+    pub unfreeze_votes: Vec<Principal>,
+}
+
+/// Replaces the principal of the participant by `new_principal` after confirmation and a veto delay.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct PrincipalRotation {
+    pub participant: Principal,
+    pub new_principal: Principal,
+    pub proposed: TimestampMillis,
+    pub confirmed: Option<TimestampMillis>,
+    pub completed: Option<TimestampMillis>,
+}
+
+/// Restricts call proposals. Calls to canisters without a rule are allowed unless the method is denied.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct CallPolicy {
+    pub rules: Vec<CanisterCallRule>,
+    pub denied_methods: Vec<DeniedMethod>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CanisterCallRule {
+    pub canister_id: Principal,
+    pub allowed_methods: Option<Vec<String>>,
+    pub max_payment: Option<u64>,
+}
+
+/// Denies the method of the canister, or of every canister when `canister_id` is not set.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct DeniedMethod {
+    pub canister_id: Option<Principal>,
+    pub method: String,
+}
+
+/// What happens to not performed proposals created under an older governance version when the governance changes.
+/// Proposals are kept when the policy is not set.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum StaleProposalPolicy {
+    Keep,
+    Reevaluate,
+    Invalidate,
+}
+
+/// Managed canisters with a balance below `low_watermark` are topped up by `top_up_amount` from the governance balance.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CyclesTopUpPolicy {
+    pub low_watermark: u128,
+    pub top_up_amount: u128,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ManagedCanister {
+    pub canister_id: Principal,
+    pub name: String,
+    pub uploader_id: Principal,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct VotingConfig {
     pub stop_vote_count: u32,
     pub positive_vote_count: u32,
 }
 
-#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+/// Participants get the permissions of their roles in addition to their own `proposal_permissions`.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct GovernanceParticipant {
     pub name: String,
     pub proposal_permissions: Vec<(ProposalType, Vec<ProposalPermission>)>,
+    pub roles: Option<Vec<String>>,
+    pub vote_public_key: Option<VotePublicKey>,
+}
+
+/// DER encoded public key, the participant signs votes relayed by others with the matching private key.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum VotePublicKey {
+    Ed25519 { public_key_der: Vec<u8> },
+    Secp256k1 { public_key_der: Vec<u8> },
+}
+
+/// Permissions of a role with `canister_scope` apply only to proposals targeting canisters in the scope.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceRole {
+    pub name: String,
+    pub proposal_permissions: Vec<(ProposalType, Vec<ProposalPermission>)>,
+    pub canister_scope: Option<Vec<CanisterScope>>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum CanisterScope {
+    Canister { canister_id: Principal },
+    Group { name: String },
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct CanisterGroup {
+    pub name: String,
+    pub canister_ids: Vec<Principal>,
+}
+
+/// Overrides the voting config of the proposal type for proposals targeting canisters in the scope.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ScopedVotingConfig {
+    pub scope: CanisterScope,
+    pub proposal_type: ProposalType,
+    pub voting_config: VotingConfig,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, Ord, PartialOrd, PartialEq)]
@@ -31,4 +156,35 @@ pub enum ProposalType {
     UpdateGovernance,
     UpgradeCanister,
     CallCanister,
+    DepositCycles,
+    UpdateApprovedModuleHashes,
+}
+
+/// A granular change of the governance, applied to the governance current at perform time.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum GovernancePatch {
+    AddParticipant {
+        participant: Principal,
+        governance_participant: GovernanceParticipant,
+    },
+    RemoveParticipant {
+        participant: Principal,
+    },
+    ChangePermissions {
+        participant: Principal,
+        proposal_permissions: Vec<(ProposalType, Vec<ProposalPermission>)>,
+    },
+    ChangeRoles {
+        participant: Principal,
+        roles: Vec<String>,
+    },
+    /// Registers, rotates or removes (with `None`) the key the participant signs relayed votes with.
+    ChangeVotePublicKey {
+        participant: Principal,
+        vote_public_key: Option<VotePublicKey>,
+    },
+    ChangeVotingConfig {
+        proposal_type: ProposalType,
+        voting_config: VotingConfig,
+    },
 }
@@ -1,7 +1,9 @@
 mod api;
+mod canister_status;
 mod governance;
 mod proposal;
 
 pub use api::*;
+pub use canister_status::*;
 pub use governance::*;
 pub use proposal::*;
@@ -1,4 +1,4 @@
-use crate::types::{Governance, ProposalType};
+use crate::types::{Governance, GovernancePatch, GovernanceVersion, ProposalType};
 use candid::{CandidType, Principal};
 use serde::{Deserialize, Serialize};
 
@@ -15,6 +15,13 @@ pub struct Proposal {
     pub updated: TimestampMillis,
     pub state: ProposalState,
     pub voting: Voting,
+    /// The governance version the proposal was created under, the base version of a governance patch.
+    pub governance_version: Option<GovernanceVersion>,
+    /// The governance version the votes were last re-evaluated under by the stale proposal policy.
+    pub reevaluated_version: Option<GovernanceVersion>,
+    /// Set for proposals targeting the governance canister, its controllers or its uploader,
+    /// voted with `Governance.self_targeting_voting_config`.
+    pub self_targeting: Option<bool>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -22,7 +29,14 @@ pub enum ProposalState {
     Voting,
     Declined,
     Approved,
-    Performed { result: PerformResult },
+    /// The proposal task is being performed, no other perform call is accepted meanwhile.
+    Performing,
+    Performed {
+        result: PerformResult,
+    },
+    Invalidated {
+        governance_version: GovernanceVersion,
+    },
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -43,26 +57,59 @@ pub struct Voting {
     pub votes: Vec<Vote>,
 }
 
+/// A vote cast by `participant`, or by `participant` as the delegate of `on_behalf_of`.
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct Vote {
     pub participant: Principal,
     pub vote_time: TimestampMillis,
     pub vote: bool,
+    pub on_behalf_of: Option<Principal>,
+}
+
+impl Vote {
+    pub fn get_voter(&self) -> Principal {
+        self.on_behalf_of.unwrap_or(self.participant)
+    }
+}
+
+/// Delegates the vote for all or the listed proposal types until `expiration`.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct VoteDelegation {
+    pub delegate: Principal,
+    pub proposal_types: Option<Vec<ProposalType>>,
+    pub expiration: TimestampMillis,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub enum ProposalDetail {
     UpdateGovernance { new_governance: Governance },
+    PatchGovernance { patch: GovernancePatch },
     UpgradeCanister { task: UpgradeCanister },
     CallCanister { task: CallCanister },
+    DepositCycles { task: DepositCycles },
+    UpdateApprovedModuleHashes { task: UpdateApprovedModuleHashes },
 }
 
 impl From<&ProposalDetail> for ProposalType {
     fn from(detail: &ProposalDetail) -> Self {
         match detail {
-            ProposalDetail::UpdateGovernance { .. } => ProposalType::UpdateGovernance,
+            ProposalDetail::UpdateGovernance { .. } | ProposalDetail::PatchGovernance { .. } => ProposalType::UpdateGovernance,
             ProposalDetail::UpgradeCanister { .. } => ProposalType::UpgradeCanister,
             ProposalDetail::CallCanister { .. } => ProposalType::CallCanister,
+            ProposalDetail::DepositCycles { .. } => ProposalType::DepositCycles,
+            ProposalDetail::UpdateApprovedModuleHashes { .. } => ProposalType::UpdateApprovedModuleHashes,
+        }
+    }
+}
+
+impl ProposalDetail {
+    pub fn get_target_canister(&self) -> Option<Principal> {
+        match self {
+            ProposalDetail::UpdateGovernance { .. } | ProposalDetail::PatchGovernance { .. } => None,
+            ProposalDetail::UpgradeCanister { task } => Some(task.canister_id),
+            ProposalDetail::CallCanister { task } => Some(task.canister_id),
+            ProposalDetail::DepositCycles { task } => Some(task.canister_id),
+            ProposalDetail::UpdateApprovedModuleHashes { task } => Some(task.canister_id),
         }
     }
 }
@@ -74,6 +121,38 @@ pub struct UpgradeCanister {
     pub operator_id: Principal,
     pub module_hash: String,
     pub argument_candid: String,
+    pub take_snapshot: Option<bool>,
+    pub health_check: Option<UpgradeHealthCheck>,
+    pub source: Option<UpgradeSource>,
+    pub wasm_source: Option<UpgradeWasmSource>,
+    pub canister_did: Option<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum UpgradeWasmSource {
+    /// The operator uploads the wasm module after the proposal is approved.
+    Operator,
+    /// The wasm module is staged in the uploader registry before the proposal is created,
+    /// governance installs it itself on perform.
+    Staged,
+}
+
+/// Where the wasm module comes from, so voters can rebuild it and compare the hash.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct UpgradeSource {
+    pub repository: String,
+    pub commit: String,
+    pub build_script: Option<String>,
+    pub wasm_length: Option<u64>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct UpgradeHealthCheck {
+    pub method: String,
+    pub argument_candid: String,
+    pub canister_did: String,
+    pub expected_response: String,
+    pub rollback_on_failure: Option<bool>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -84,3 +163,16 @@ pub struct CallCanister {
     pub payment: Option<u64>,
     pub canister_did: Option<String>,
 }
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct DepositCycles {
+    pub canister_id: Principal,
+    pub amount: u128,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct UpdateApprovedModuleHashes {
+    pub canister_id: Principal,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
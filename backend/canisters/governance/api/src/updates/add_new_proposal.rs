@@ -1,4 +1,4 @@
-use crate::types::{Proposal, ProposalDetail, ProposalId};
+use crate::types::{Proposal, ProposalDetail, ProposalId, TimestampMillis};
 use candid::CandidType;
 use serde::Deserialize;
 
@@ -28,4 +28,5 @@ pub struct AddNewProposalResult {
 pub enum AddNewProposalError {
     NotPermission,
     Validation { reason: String },
+    GovernanceFrozen { until: TimestampMillis },
 }
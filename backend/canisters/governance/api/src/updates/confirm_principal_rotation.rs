@@ -0,0 +1,31 @@
+use crate::types::{PrincipalRotation, TimestampMillis};
+use candid::{CandidType, Principal};
+use serde::Deserialize;
+
+pub type Args = ConfirmPrincipalRotationArgs;
+pub type Response = ConfirmPrincipalRotationResponse;
+
+/// Called by the new principal of the rotation.
+#[derive(CandidType, Deserialize, Debug)]
+pub struct ConfirmPrincipalRotationArgs {
+    pub participant: Principal,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum ConfirmPrincipalRotationResponse {
+    Ok(ConfirmPrincipalRotationResult),
+    Err(ConfirmPrincipalRotationError),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct ConfirmPrincipalRotationResult {
+    pub rotation: PrincipalRotation,
+    pub effective_time: TimestampMillis,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum ConfirmPrincipalRotationError {
+    RotationNotFound,
+    NotNewPrincipal,
+    AlreadyConfirmed,
+}
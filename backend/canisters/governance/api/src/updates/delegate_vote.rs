@@ -0,0 +1,25 @@
+use crate::types::VoteDelegation;
+use candid::CandidType;
+use serde::Deserialize;
+
+pub type Args = DelegateVoteArgs;
+pub type Response = DelegateVoteResponse;
+
+/// Replaces the delegation of the caller, or revokes it when `delegation` is not set.
+#[derive(CandidType, Deserialize, Debug)]
+pub struct DelegateVoteArgs {
+    pub delegation: Option<VoteDelegation>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum DelegateVoteResponse {
+    Ok,
+    Err(DelegateVoteError),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum DelegateVoteError {
+    DelegateNotParticipant,
+    SelfDelegation,
+    WrongExpiration,
+}
@@ -0,0 +1,31 @@
+use crate::types::{GovernanceFreeze, TimestampMillis};
+use candid::CandidType;
+use serde::Deserialize;
+
+pub type Args = FreezeGovernanceArgs;
+pub type Response = FreezeGovernanceResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct FreezeGovernanceArgs {
+    pub duration_millis: u64,
+    pub reason: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum FreezeGovernanceResponse {
+    Ok(FreezeGovernanceResult),
+    Err(FreezeGovernanceError),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct FreezeGovernanceResult {
+    pub freeze: GovernanceFreeze,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum FreezeGovernanceError {
+    NotGuardian,
+    AlreadyFrozen,
+    WrongDuration { max_duration_millis: u64 },
+    CoolingDown { until: TimestampMillis },
+}
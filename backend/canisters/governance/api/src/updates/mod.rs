@@ -1,5 +1,12 @@
 pub mod add_new_proposal;
+pub mod confirm_principal_rotation;
+pub mod delegate_vote;
+pub mod freeze_governance;
 pub mod perform_proposal;
+pub mod propose_principal_rotation;
+pub mod refresh_canister_did;
 pub mod set_geek_user_principals;
+pub mod unfreeze_governance;
 pub mod update_canistergeek_information;
+pub mod veto_principal_rotation;
 pub mod vote_for_proposal;
@@ -1,4 +1,4 @@
-use crate::types::{Proposal, ProposalId};
+use crate::types::{Proposal, ProposalId, TimestampMillis};
 use candid::CandidType;
 use serde::Deserialize;
 
@@ -27,4 +27,5 @@ pub enum PerformProposalError {
     ProposalNotFound,
     ProposalIsNotApprovedState,
     NotPermission,
+    GovernanceFrozen { until: TimestampMillis },
 }
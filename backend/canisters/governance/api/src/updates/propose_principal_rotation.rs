@@ -0,0 +1,29 @@
+use crate::types::PrincipalRotation;
+use candid::{CandidType, Principal};
+use serde::Deserialize;
+
+pub type Args = ProposePrincipalRotationArgs;
+pub type Response = ProposePrincipalRotationResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct ProposePrincipalRotationArgs {
+    pub new_principal: Principal,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum ProposePrincipalRotationResponse {
+    Ok(ProposePrincipalRotationResult),
+    Err(ProposePrincipalRotationError),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct ProposePrincipalRotationResult {
+    pub rotation: PrincipalRotation,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum ProposePrincipalRotationError {
+    AnonymousPrincipal,
+    NewPrincipalIsParticipant,
+    NewPrincipalIsPending,
+}
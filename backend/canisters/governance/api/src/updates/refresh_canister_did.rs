@@ -0,0 +1,30 @@
+use candid::{CandidType, Principal};
+use serde::Deserialize;
+
+pub type Args = RefreshCanisterDidArgs;
+pub type Response = RefreshCanisterDidResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct RefreshCanisterDidArgs {
+    pub canister_id: Principal,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum RefreshCanisterDidResponse {
+    Ok(RefreshCanisterDidResult),
+    Err(RefreshCanisterDidError),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct RefreshCanisterDidResult {
+    pub module_hash: String,
+    pub canister_did: String,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum RefreshCanisterDidError {
+    CanisterNotManaged,
+    ModuleHashUnknown,
+    CandidNotFound,
+    CallError { reason: String },
+}
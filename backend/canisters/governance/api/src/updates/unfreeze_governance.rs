@@ -0,0 +1,26 @@
+use crate::types::{EmptyArgs, GovernanceFreeze};
+use candid::CandidType;
+use serde::Deserialize;
+
+pub type Args = EmptyArgs;
+pub type Response = UnfreezeGovernanceResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum UnfreezeGovernanceResponse {
+    Ok(UnfreezeGovernanceResult),
+    Err(UnfreezeGovernanceError),
+}
+
+/// The freeze is still active while `unfreeze_votes` do not reach the quorum.
+#[derive(CandidType, Deserialize, Debug)]
+pub enum UnfreezeGovernanceResult {
+    Unfrozen,
+    Voted { freeze: GovernanceFreeze },
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum UnfreezeGovernanceError {
+    NotGuardian,
+    NotFrozen,
+    AlreadyVoted,
+}
@@ -0,0 +1,27 @@
+use crate::types::PrincipalRotation;
+use candid::{CandidType, Principal};
+use serde::Deserialize;
+
+pub type Args = VetoPrincipalRotationArgs;
+pub type Response = VetoPrincipalRotationResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct VetoPrincipalRotationArgs {
+    pub participant: Principal,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum VetoPrincipalRotationResponse {
+    Ok(VetoPrincipalRotationResult),
+    Err(VetoPrincipalRotationError),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct VetoPrincipalRotationResult {
+    pub rotation: PrincipalRotation,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum VetoPrincipalRotationError {
+    RotationNotFound,
+}
@@ -1,5 +1,5 @@
-use crate::types::{Proposal, ProposalId};
-use candid::CandidType;
+use crate::types::{GovernanceVersion, Proposal, ProposalId};
+use candid::{CandidType, Principal};
 use serde::Deserialize;
 
 pub type Args = VoteForProposalArgs;
@@ -9,6 +9,14 @@ pub type Response = VoteForProposalResponse;
 pub struct VoteForProposalArgs {
     pub proposal_id: ProposalId,
     pub vote: bool,
+    pub signed_vote: Option<SignedVote>,
+}
+
+/// A vote of `participant` submitted by any caller, signed over the message of `get_vote_signing_message`.
+#[derive(CandidType, Deserialize, Debug)]
+pub struct SignedVote {
+    pub participant: Principal,
+    pub signature: Vec<u8>,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -30,4 +38,29 @@ pub enum VoteForProposalError {
     VotingConfigNotFound,
     AlreadyVoted,
     NotPermission,
+    VotePublicKeyNotRegistered,
+    WrongSignature { reason: String },
+}
+
+const VOTE_SIGNING_DOMAIN: &[u8] = b"governance-vote";
+
+/// The governance canister id and the current governance version make the signature
+/// invalid for other governance canisters and after the governance changes.
+pub fn get_vote_signing_message(
+    governance_id: &Principal,
+    proposal_id: ProposalId,
+    vote: bool,
+    governance_version: GovernanceVersion,
+) -> Vec<u8> {
+    let governance_id = governance_id.as_slice();
+
+    let mut message = Vec::new();
+    message.push(VOTE_SIGNING_DOMAIN.len() as u8);
+    message.extend_from_slice(VOTE_SIGNING_DOMAIN);
+    message.push(governance_id.len() as u8);
+    message.extend_from_slice(governance_id);
+    message.extend_from_slice(&proposal_id.to_be_bytes());
+    message.push(vote as u8);
+    message.extend_from_slice(&governance_version.to_be_bytes());
+    message
 }
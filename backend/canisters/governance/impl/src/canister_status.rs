@@ -0,0 +1,152 @@
+use crate::time::get_unix_epoch_time_millis;
+use crate::updates::refresh_canister_did::refresh_canister_did_int;
+use crate::{log_error, log_info, mutate_state, read_state};
+use candid::{Nat, Principal};
+use governance_canister::types::{CanisterStatusSummary, ManagedCanister};
+use ic_cdk::management_canister::DepositCyclesArgs;
+use std::time::Duration;
+use uploader_canister::get_canister_status::{GetCanisterStatusArgs, GetCanisterStatusResponse};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10 * 60);
+/// A status older than two refresh rounds is not trusted for a top up.
+const MAX_TOP_UP_STATUS_AGE_MILLIS: u64 = 2 * REFRESH_INTERVAL.as_millis() as u64;
+/// Cycles kept above the freezing threshold so governance can keep executing after deposits.
+const TOP_UP_RESERVE_CYCLES: u128 = 100_000_000_000;
+
+pub(crate) fn start_refresh_timer() {
+    ic_cdk_timers::set_timer(Duration::ZERO, refresh_canister_statuses());
+    ic_cdk_timers::set_timer_interval(REFRESH_INTERVAL, refresh_canister_statuses);
+}
+
+async fn refresh_canister_statuses() {
+    let managed_canisters: Vec<ManagedCanister> =
+        read_state(|state| state.model.governance_storage.get_managed_canisters().to_vec());
+
+    mutate_state(|state| {
+        let canister_ids: Vec<Principal> = managed_canisters.iter().map(|canister| canister.canister_id).collect();
+        state.model.canister_status_storage.retain_canisters(&canister_ids);
+    });
+
+    for canister in managed_canisters {
+        let status = get_canister_status(&canister).await;
+        if let Err(error) = &status {
+            log_error!("Can not refresh status of canister '{}': {error}", canister.canister_id);
+        }
+
+        mutate_state(|state| state.model.canister_status_storage.set_status(canister.canister_id, status));
+    }
+
+    log_info!("Managed canister statuses refreshed");
+
+    refresh_changed_canister_dids().await;
+
+    top_up_canisters().await;
+}
+
+/// Keeps cached candid interfaces in line with the modules the canisters run now.
+async fn refresh_changed_canister_dids() {
+    let canister_ids: Vec<Principal> = read_state(|state| {
+        state
+            .model
+            .governance_storage
+            .get_managed_canisters()
+            .iter()
+            .map(|canister| canister.canister_id)
+            .filter(|canister_id| {
+                let module_hash = state
+                    .model
+                    .canister_status_storage
+                    .get_entry(canister_id)
+                    .and_then(|entry| entry.status.as_ref())
+                    .and_then(|status| status.module_hash.as_ref());
+                let did_module_hash = state
+                    .model
+                    .canister_did_storage
+                    .get_did(canister_id)
+                    .map(|did| &did.module_hash);
+                module_hash.is_some() && module_hash != did_module_hash
+            })
+            .collect()
+    });
+
+    for canister_id in canister_ids {
+        if let Err(error) = refresh_canister_did_int(canister_id).await {
+            log_info!("Candid of canister '{canister_id}' is not refreshed: {error:?}");
+        }
+    }
+}
+
+async fn top_up_canisters() {
+    let Some((policy, canister_ids)) = read_state(|state| {
+        let policy = state.model.governance_storage.get_governance().cycles_top_up.clone()?;
+        let low_watermark = Nat::from(policy.low_watermark);
+        let now = get_unix_epoch_time_millis();
+        let canister_ids: Vec<Principal> = state
+            .model
+            .governance_storage
+            .get_managed_canisters()
+            .iter()
+            .map(|canister| canister.canister_id)
+            .filter(|canister_id| {
+                state
+                    .model
+                    .canister_status_storage
+                    .get_entry(canister_id)
+                    .filter(|entry| entry.error.is_none())
+                    .and_then(|entry| entry.status.as_ref())
+                    .filter(|status| now.saturating_sub(status.updated) <= MAX_TOP_UP_STATUS_AGE_MILLIS)
+                    .is_some_and(|status| status.cycles < low_watermark)
+            })
+            .collect();
+        Some((policy, canister_ids))
+    }) else {
+        return;
+    };
+
+    for canister_id in canister_ids {
+        if let Err(error) = check_cycles_reserve(policy.top_up_amount) {
+            log_error!("Can not top up canister '{canister_id}': {error}");
+            return;
+        }
+
+        match deposit_cycles(canister_id, policy.top_up_amount).await {
+            Ok(_) => log_info!("Canister '{canister_id}' topped up with {} cycles", policy.top_up_amount),
+            Err(error) => log_error!("Can not top up canister '{canister_id}': {error}"),
+        }
+    }
+}
+
+pub(crate) fn check_cycles_reserve(amount: u128) -> Result<(), String> {
+    let balance = ic_cdk::api::canister_liquid_cycle_balance();
+    if balance < amount.saturating_add(TOP_UP_RESERVE_CYCLES) {
+        return Err(format!("governance balance {balance} is too low"));
+    }
+    Ok(())
+}
+
+pub(crate) async fn deposit_cycles(canister_id: Principal, amount: u128) -> Result<(), String> {
+    ic_cdk::management_canister::deposit_cycles(&DepositCyclesArgs { canister_id }, amount)
+        .await
+        .map_err(|error| format!("error while deposit cycles: {error:?}"))
+}
+
+async fn get_canister_status(canister: &ManagedCanister) -> Result<CanisterStatusSummary, String> {
+    let response: GetCanisterStatusResponse = ic_cdk::call::Call::bounded_wait(canister.uploader_id, "get_canister_status")
+        .with_arg(GetCanisterStatusArgs {
+            canister_id: canister.canister_id,
+        })
+        .await
+        .map_err(|error| format!("error while perform uploader canister call: {error:?}"))?
+        .candid()
+        .map_err(|error| format!("can not decode uploader response: {error:?}"))?;
+
+    match response {
+        GetCanisterStatusResponse::Ok(result) => Ok(CanisterStatusSummary {
+            module_hash: result.status.module_hash,
+            cycles: result.status.cycles,
+            controllers: result.status.controllers,
+            updated: get_unix_epoch_time_millis(),
+        }),
+        GetCanisterStatusResponse::Err(error) => Err(format!("uploader get_canister_status error: {error:?}")),
+    }
+}
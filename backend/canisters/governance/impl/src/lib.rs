@@ -1,9 +1,12 @@
+mod canister_status;
 mod guards;
 mod lifecycle;
 mod macros;
 mod model;
+mod principal_rotation;
 mod queries;
 mod serializer;
+mod signature;
 mod state;
 mod time;
 mod updates;
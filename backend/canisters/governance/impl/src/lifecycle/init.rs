@@ -1,4 +1,6 @@
+use crate::canister_status::start_refresh_timer;
 use crate::model::DataModel;
+use crate::principal_rotation::start_rotation_timer;
 use crate::state::CanisterState;
 use crate::{init_state, log_info, mutate_state};
 use governance_canister::init::Args;
@@ -20,6 +22,9 @@ fn init(args: Args) {
             .set_geek_user_principals(args.geek_user_principals);
     });
 
+    start_refresh_timer();
+    start_rotation_timer();
+
     log_info!("Governance initialized!");
 }
 
@@ -1,9 +1,11 @@
+use crate::canister_status::start_refresh_timer;
 use crate::lifecycle::init::init_http_assets;
 use crate::lifecycle::pre_upgrade::StateVersion;
 use crate::model::DataModel;
+use crate::principal_rotation::start_rotation_timer;
 use crate::serializer::deserialize;
 use crate::state::CanisterState;
-use crate::{init_state, log_info};
+use crate::{init_state, log_info, mutate_state};
 use ic_cdk_macros::post_upgrade;
 
 #[post_upgrade]
@@ -25,7 +27,13 @@ fn post_upgrade() {
         }
     };
 
+    if let Some(config) = mutate_state(|state| state.model.governance_storage.migrate_self_targeting_voting_config().cloned()) {
+        log_info!("Self-targeting voting config is migrated: {config:?}");
+    }
+
     init_http_assets();
+    start_refresh_timer();
+    start_rotation_timer();
 
     log_info!("Governance post-upgrade completed!");
 }
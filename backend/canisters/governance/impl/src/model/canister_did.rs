@@ -0,0 +1,28 @@
+use candid::Principal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct CanisterDidStorage {
+    dids: BTreeMap<Principal, CanisterDid>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub(crate) struct CanisterDid {
+    pub module_hash: String,
+    pub candid: String,
+}
+
+impl CanisterDidStorage {
+    pub(crate) fn get_did(&self, canister_id: &Principal) -> Option<&CanisterDid> {
+        self.dids.get(canister_id)
+    }
+
+    pub(crate) fn get_candid(&self, canister_id: &Principal) -> Option<&String> {
+        self.dids.get(canister_id).map(|did| &did.candid)
+    }
+
+    pub(crate) fn set_did(&mut self, canister_id: Principal, did: CanisterDid) {
+        self.dids.insert(canister_id, did);
+    }
+}
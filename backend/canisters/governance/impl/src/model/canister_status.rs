@@ -0,0 +1,37 @@
+use candid::Principal;
+use governance_canister::types::CanisterStatusSummary;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct CanisterStatusStorage {
+    statuses: BTreeMap<Principal, CanisterStatusEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub(crate) struct CanisterStatusEntry {
+    pub status: Option<CanisterStatusSummary>,
+    pub error: Option<String>,
+}
+
+impl CanisterStatusStorage {
+    pub(crate) fn get_entry(&self, canister_id: &Principal) -> Option<&CanisterStatusEntry> {
+        self.statuses.get(canister_id)
+    }
+
+    /// Keeps the last known status, so a failed refresh only records the error.
+    pub(crate) fn set_status(&mut self, canister_id: Principal, status: Result<CanisterStatusSummary, String>) {
+        let entry = self.statuses.entry(canister_id).or_default();
+        match status {
+            Ok(status) => {
+                entry.status = Some(status);
+                entry.error = None;
+            }
+            Err(error) => entry.error = Some(error),
+        }
+    }
+
+    pub(crate) fn retain_canisters(&mut self, canister_ids: &[Principal]) {
+        self.statuses.retain(|canister_id, _| canister_ids.contains(canister_id));
+    }
+}
@@ -0,0 +1,128 @@
+use candid::Principal;
+use governance_canister::types::{GovernanceFreeze, TimestampMillis};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct FreezeStorage {
+    freeze: Option<GovernanceFreeze>,
+}
+
+impl FreezeStorage {
+    pub(crate) fn set_freeze(&mut self, freeze: GovernanceFreeze) {
+        self.freeze = Some(freeze);
+    }
+
+    /// The last freeze is kept with `until` moved to now, so the cooldown is counted from the unfreeze.
+    pub(crate) fn end_freeze(&mut self, time: TimestampMillis) {
+        if let Some(freeze) = self.freeze.as_mut() {
+            freeze.until = freeze.until.min(time);
+        }
+    }
+
+    /// An expired freeze is not active anymore, no unfreeze is needed.
+    pub(crate) fn get_active_freeze(&self, time: TimestampMillis) -> Option<&GovernanceFreeze> {
+        self.freeze.as_ref().filter(|freeze| freeze.until > time)
+    }
+
+    pub(crate) fn get_active_freeze_mut(&mut self, time: TimestampMillis) -> Option<&mut GovernanceFreeze> {
+        self.freeze.as_mut().filter(|freeze| freeze.until > time)
+    }
+
+    /// Keeps the freeze initiator and unfreeze votes of a rotated guardian linked to its new principal.
+    pub(crate) fn rotate_principal(&mut self, old_principal: &Principal, new_principal: Principal) {
+        if let Some(freeze) = self.freeze.as_mut() {
+            if freeze.guardian == *old_principal {
+                freeze.guardian = new_principal;
+            }
+
+            if freeze.unfreeze_votes.contains(&new_principal) {
+                freeze.unfreeze_votes.retain(|guardian| guardian != old_principal);
+            } else if let Some(vote) = freeze.unfreeze_votes.iter_mut().find(|vote| *vote == old_principal) {
+                *vote = new_principal;
+            }
+        }
+    }
+
+    /// A new freeze is possible only `cooldown` after the last one ended,
+    /// so the governance always has time to replace a compromised guardian.
+    pub(crate) fn get_cooldown_end(&self, time: TimestampMillis, cooldown: u64) -> Option<TimestampMillis> {
+        self.freeze
+            .as_ref()
+            .map(|freeze| freeze.until.saturating_add(cooldown))
+            .filter(|cooldown_end| *cooldown_end > time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::freeze::FreezeStorage;
+    use candid::Principal;
+    use governance_canister::types::{GovernanceFreeze, TimestampMillis};
+
+    const DURATION: u64 = 1_000;
+
+    fn freeze(started: TimestampMillis) -> GovernanceFreeze {
+        GovernanceFreeze {
+            guardian: Principal::from_slice(&[1]),
+            reason: None,
+            started,
+            until: started + DURATION,
+            unfreeze_votes: vec![],
+        }
+    }
+
+    #[test]
+    fn test_freeze_expiry_and_cooldown() {
+        let mut storage = FreezeStorage::default();
+        assert!(storage.get_active_freeze(0).is_none());
+        assert_eq!(storage.get_cooldown_end(0, DURATION), None);
+
+        storage.set_freeze(freeze(100));
+        assert!(storage.get_active_freeze(100).is_some());
+        assert!(storage.get_active_freeze(100 + DURATION - 1).is_some());
+        assert!(storage.get_active_freeze(100 + DURATION).is_none());
+
+        // the freeze expired, but the next one has to wait for the cooldown
+        let expired = 100 + DURATION;
+        assert_eq!(storage.get_cooldown_end(expired, DURATION), Some(expired + DURATION));
+        assert_eq!(storage.get_cooldown_end(expired + DURATION, DURATION), None);
+    }
+
+    #[test]
+    fn test_unfreeze_starts_cooldown() {
+        let mut storage = FreezeStorage::default();
+        storage.set_freeze(freeze(100));
+
+        let guardian = Principal::from_slice(&[2]);
+        storage.get_active_freeze_mut(200).unwrap().unfreeze_votes.push(guardian);
+        assert_eq!(storage.get_active_freeze(200).unwrap().unfreeze_votes, vec![guardian]);
+
+        storage.end_freeze(300);
+        assert!(storage.get_active_freeze(300).is_none());
+        assert_eq!(storage.get_cooldown_end(300, DURATION), Some(300 + DURATION));
+
+        // an already expired freeze is not extended
+        storage.end_freeze(100 + 2 * DURATION);
+        assert_eq!(storage.get_cooldown_end(300, DURATION), Some(300 + DURATION));
+    }
+
+    #[test]
+    fn test_rotate_guardian_in_freeze() {
+        let mut storage = FreezeStorage::default();
+        let old_principal = Principal::from_slice(&[1]);
+        let new_principal = Principal::from_slice(&[3]);
+
+        storage.set_freeze(freeze(100));
+        storage
+            .get_active_freeze_mut(200)
+            .unwrap()
+            .unfreeze_votes
+            .extend([old_principal, Principal::from_slice(&[2])]);
+
+        storage.rotate_principal(&old_principal, new_principal);
+
+        let freeze = storage.get_active_freeze(200).unwrap();
+        assert_eq!(freeze.guardian, new_principal);
+        assert_eq!(freeze.unfreeze_votes, vec![new_principal, Principal::from_slice(&[2])]);
+    }
+}
@@ -1,17 +1,143 @@
 use candid::Principal;
-use governance_canister::types::{Governance, GovernanceParticipant, ProposalPermission, ProposalType, VotingConfig};
+use governance_canister::types::{
+    CallCanister, CanisterScope, Governance, GovernanceParticipant, GovernancePatch, GovernanceVersion, ManagedCanister,
+    Proposal, ProposalPermission, ProposalType, VotingConfig,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct GovernanceStorage {
     governance: Governance,
+    #[serde(default)]
+    version: GovernanceVersion,
+    #[serde(default)]
+    participant_versions: BTreeMap<Principal, GovernanceVersion>,
+    #[serde(default)]
+    voting_config_versions: BTreeMap<ProposalType, GovernanceVersion>,
 }
 
 impl GovernanceStorage {
+    /// Full replacement conflicts with every pending patch, so all entities are stamped with the new version.
     pub(crate) fn set_new_governance(&mut self, new_governance: Governance) {
+        self.version += 1;
+
+        let version = self.version;
+        self.governance
+            .participants
+            .iter()
+            .chain(new_governance.participants.iter())
+            .for_each(|(principal, _)| {
+                self.participant_versions.insert(*principal, version);
+            });
+        self.governance
+            .voting_configuration
+            .iter()
+            .chain(new_governance.voting_configuration.iter())
+            .for_each(|(proposal_type, _)| {
+                self.voting_config_versions.insert(proposal_type.clone(), version);
+            });
+
         self.governance = new_governance;
     }
 
+    /// Governances created before the self-targeting config existed get the config of governance proposals,
+    /// the strictest one allowed by validation, so their patches keep passing it.
+    pub(crate) fn migrate_self_targeting_voting_config(&mut self) -> Option<&VotingConfig> {
+        if self.governance.self_targeting_voting_config.is_some() {
+            return None;
+        }
+
+        self.governance.self_targeting_voting_config = self
+            .governance
+            .voting_configuration
+            .iter()
+            .find(|(proposal_type, _)| proposal_type == &ProposalType::UpdateGovernance)
+            .map(|(_, config)| config.clone());
+        self.governance.self_targeting_voting_config.as_ref()
+    }
+
+    pub(crate) fn get_version(&self) -> GovernanceVersion {
+        self.version
+    }
+
+    /// Applies the patch unless the entity it touches was changed after the governance version the proposal was based on.
+    pub(crate) fn apply_patch(
+        &mut self,
+        patch: &GovernancePatch,
+        base_version: GovernanceVersion,
+        validate: impl FnOnce(&Governance) -> Result<(), String>,
+    ) -> Result<(), String> {
+        let entity_version = match patch {
+            GovernancePatch::AddParticipant { participant, .. }
+            | GovernancePatch::RemoveParticipant { participant }
+            | GovernancePatch::ChangePermissions { participant, .. }
+            | GovernancePatch::ChangeRoles { participant, .. }
+            | GovernancePatch::ChangeVotePublicKey { participant, .. } => self.participant_versions.get(participant),
+            GovernancePatch::ChangeVotingConfig { proposal_type, .. } => self.voting_config_versions.get(proposal_type),
+        };
+
+        if entity_version.is_some_and(|version| *version > base_version) {
+            return Err(format!(
+                "governance patch conflicts with changes made after version {base_version}"
+            ));
+        }
+
+        let governance = patch_governance(&self.governance, patch)?;
+        validate(&governance)?;
+
+        self.version += 1;
+        match patch {
+            GovernancePatch::AddParticipant { participant, .. }
+            | GovernancePatch::RemoveParticipant { participant }
+            | GovernancePatch::ChangePermissions { participant, .. }
+            | GovernancePatch::ChangeRoles { participant, .. }
+            | GovernancePatch::ChangeVotePublicKey { participant, .. } => {
+                self.participant_versions.insert(*participant, self.version);
+            }
+            GovernancePatch::ChangeVotingConfig { proposal_type, .. } => {
+                self.voting_config_versions.insert(proposal_type.clone(), self.version);
+            }
+        }
+        self.governance = governance;
+
+        Ok(())
+    }
+
+    /// Keeps the name, permissions, roles and guardian rights of the participant under the new principal.
+    /// Membership changes, so the governance version is bumped like for a patch.
+    pub(crate) fn rotate_participant(&mut self, old_principal: &Principal, new_principal: Principal) -> Result<(), String> {
+        if self.get_governance_participant(&new_principal).is_some() {
+            return Err(format!("principal '{new_principal}' is already participant"));
+        }
+
+        let (principal, _) = self
+            .governance
+            .participants
+            .iter_mut()
+            .find(|(p, _)| p == old_principal)
+            .ok_or(format!("participant '{old_principal}' not found"))?;
+        *principal = new_principal;
+
+        if let Some(guardian_config) = self.governance.guardians.as_mut() {
+            if guardian_config.guardians.contains(&new_principal) {
+                guardian_config.guardians.retain(|guardian| guardian != old_principal);
+            } else if let Some(guardian) = guardian_config
+                .guardians
+                .iter_mut()
+                .find(|guardian| *guardian == old_principal)
+            {
+                *guardian = new_principal;
+            }
+        }
+
+        self.version += 1;
+        self.participant_versions.insert(*old_principal, self.version);
+        self.participant_versions.insert(new_principal, self.version);
+
+        Ok(())
+    }
+
     pub(crate) fn get_governance(&self) -> &Governance {
         &self.governance
     }
@@ -28,21 +154,117 @@ impl GovernanceStorage {
         &self,
         principal: &Principal,
         proposal_type: &ProposalType,
+        target_canister: Option<&Principal>,
         permission: &ProposalPermission,
     ) -> bool {
         self.get_governance_participant(principal)
-            .map(|participant| {
-                participant
-                    .proposal_permissions
-                    .iter()
-                    .find(|(pt, _)| pt == proposal_type)
-                    .map(|(_, permissions)| permissions.contains(permission))
-                    .unwrap_or(false)
-            })
+            .map(|participant| has_permission(&self.governance, participant, proposal_type, target_canister, permission))
             .unwrap_or(false)
     }
 
-    pub(crate) fn get_voting_configuration(&self, proposal_type: &ProposalType) -> Option<&VotingConfig> {
+    pub(crate) fn check_call_policy(&self, task: &CallCanister) -> Result<(), String> {
+        let Some(call_policy) = &self.governance.call_policy else {
+            return Ok(());
+        };
+
+        if call_policy
+            .denied_methods
+            .iter()
+            .any(|denied| denied.method == task.method && denied.canister_id.is_none_or(|id| id == task.canister_id))
+        {
+            return Err(format!(
+                "method '{}' of canister '{}' is denied by call policy",
+                task.method, task.canister_id
+            ));
+        }
+
+        let Some(rule) = call_policy.rules.iter().find(|rule| rule.canister_id == task.canister_id) else {
+            return Ok(());
+        };
+
+        if rule
+            .allowed_methods
+            .as_ref()
+            .is_some_and(|methods| !methods.contains(&task.method))
+        {
+            return Err(format!(
+                "method '{}' of canister '{}' is not allowed by call policy",
+                task.method, task.canister_id
+            ));
+        }
+
+        if let Some(max_payment) = rule.max_payment {
+            if task.payment.unwrap_or(0) > max_payment {
+                return Err(format!(
+                    "payment exceeds {max_payment} allowed by call policy for canister '{}'",
+                    task.canister_id
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn get_managed_canisters(&self) -> &[ManagedCanister] {
+        self.governance.managed_canisters.as_deref().unwrap_or_default()
+    }
+
+    pub(crate) fn get_proposal_voting_configuration(&self, proposal: &Proposal) -> Option<&VotingConfig> {
+        if proposal.self_targeting == Some(true) {
+            return self.governance.self_targeting_voting_config.as_ref();
+        }
+
+        self.get_voting_configuration(
+            &ProposalType::from(&proposal.detail),
+            proposal.detail.get_target_canister().as_ref(),
+        )
+    }
+
+    /// The governance canister, its controllers (like the uploader installing it)
+    /// and the uploader of the governance canister from the managed canisters.
+    pub(crate) fn is_self_targeting(
+        &self,
+        governance_id: &Principal,
+        target_canister: &Principal,
+        is_governance_controller: bool,
+    ) -> bool {
+        target_canister == governance_id
+            || is_governance_controller
+            || self
+                .get_managed_canisters()
+                .iter()
+                .any(|canister| &canister.canister_id == governance_id && &canister.uploader_id == target_canister)
+    }
+
+    /// A config scoped to the target canister itself wins over a config scoped to its group,
+    /// which wins over the config of the proposal type.
+    pub(crate) fn get_voting_configuration(
+        &self,
+        proposal_type: &ProposalType,
+        target_canister: Option<&Principal>,
+    ) -> Option<&VotingConfig> {
+        if let Some(canister_id) = target_canister {
+            let scoped_configs = self
+                .governance
+                .scoped_voting_configuration
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .filter(|config| {
+                    &config.proposal_type == proposal_type && is_in_scope(&self.governance, &config.scope, canister_id)
+                })
+                .collect::<Vec<_>>();
+
+            let scoped_config = scoped_configs
+                .iter()
+                .find(|config| matches!(config.scope, CanisterScope::Canister { .. }))
+                .or_else(|| scoped_configs.first());
+
+            if let Some(config) = scoped_config {
+                return Some(&config.voting_config);
+            }
+        }
+
         self.governance
             .voting_configuration
             .iter()
@@ -50,3 +272,304 @@ impl GovernanceStorage {
             .map(|(_, config)| config)
     }
 }
+
+pub(crate) fn is_in_scope(governance: &Governance, scope: &CanisterScope, canister_id: &Principal) -> bool {
+    match scope {
+        CanisterScope::Canister { canister_id: id } => id == canister_id,
+        CanisterScope::Group { name } => governance
+            .canister_groups
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .find(|group| &group.name == name)
+            .is_some_and(|group| group.canister_ids.contains(canister_id)),
+    }
+}
+
+/// Resolves the permission through the own permissions of the participant and through the permissions of its roles.
+/// Scoped roles are taken into account only for proposals targeting a canister in their scope.
+pub(crate) fn has_permission(
+    governance: &Governance,
+    participant: &GovernanceParticipant,
+    proposal_type: &ProposalType,
+    target_canister: Option<&Principal>,
+    permission: &ProposalPermission,
+) -> bool {
+    let contains = |proposal_permissions: &[(ProposalType, Vec<ProposalPermission>)]| {
+        proposal_permissions
+            .iter()
+            .any(|(pt, permissions)| pt == proposal_type && permissions.contains(permission))
+    };
+
+    if contains(&participant.proposal_permissions) {
+        return true;
+    }
+
+    let roles = governance.roles.as_deref().unwrap_or_default();
+    participant.roles.iter().flatten().any(|role_name| {
+        roles
+            .iter()
+            .find(|role| &role.name == role_name)
+            .filter(|role| match (&role.canister_scope, target_canister) {
+                (None, _) => true,
+                (Some(scopes), Some(canister_id)) => scopes.iter().any(|scope| is_in_scope(governance, scope, canister_id)),
+                (Some(_), None) => false,
+            })
+            .is_some_and(|role| contains(&role.proposal_permissions))
+    })
+}
+
+pub(crate) fn patch_governance(governance: &Governance, patch: &GovernancePatch) -> Result<Governance, String> {
+    let mut governance = governance.clone();
+
+    match patch {
+        GovernancePatch::AddParticipant {
+            participant,
+            governance_participant,
+        } => {
+            if governance.participants.iter().any(|(p, _)| p == participant) {
+                return Err(format!("participant '{participant}' already exists"));
+            }
+            governance.participants.push((*participant, governance_participant.clone()));
+        }
+        GovernancePatch::RemoveParticipant { participant } => {
+            let count = governance.participants.len();
+            governance.participants.retain(|(p, _)| p != participant);
+            if governance.participants.len() == count {
+                return Err(format!("participant '{participant}' not found"));
+            }
+        }
+        GovernancePatch::ChangePermissions {
+            participant,
+            proposal_permissions,
+        } => {
+            let (_, governance_participant) = governance
+                .participants
+                .iter_mut()
+                .find(|(p, _)| p == participant)
+                .ok_or(format!("participant '{participant}' not found"))?;
+            governance_participant.proposal_permissions = proposal_permissions.clone();
+        }
+        GovernancePatch::ChangeRoles { participant, roles } => {
+            let (_, governance_participant) = governance
+                .participants
+                .iter_mut()
+                .find(|(p, _)| p == participant)
+                .ok_or(format!("participant '{participant}' not found"))?;
+            governance_participant.roles = Some(roles.clone());
+        }
+        GovernancePatch::ChangeVotePublicKey {
+            participant,
+            vote_public_key,
+        } => {
+            let (_, governance_participant) = governance
+                .participants
+                .iter_mut()
+                .find(|(p, _)| p == participant)
+                .ok_or(format!("participant '{participant}' not found"))?;
+            governance_participant.vote_public_key = vote_public_key.clone();
+        }
+        GovernancePatch::ChangeVotingConfig {
+            proposal_type,
+            voting_config,
+        } => match governance.voting_configuration.iter_mut().find(|(pt, _)| pt == proposal_type) {
+            Some((_, config)) => *config = voting_config.clone(),
+            None => governance
+                .voting_configuration
+                .push((proposal_type.clone(), voting_config.clone())),
+        },
+    }
+
+    Ok(governance)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::governance::GovernanceStorage;
+    use candid::Principal;
+    use governance_canister::types::{
+        CallCanister, CallPolicy, CanisterCallRule, CanisterGroup, CanisterScope, DeniedMethod, Governance,
+        GovernanceParticipant, GovernancePatch, GovernanceRole, ProposalPermission, ProposalType, ScopedVotingConfig,
+        VotingConfig,
+    };
+
+    fn add_participant(id: u8) -> GovernancePatch {
+        GovernancePatch::AddParticipant {
+            participant: Principal::from_slice(&[id]),
+            governance_participant: GovernanceParticipant {
+                name: format!("participant {id}"),
+                proposal_permissions: vec![],
+                roles: None,
+                vote_public_key: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_concurrent_patches_of_different_participants() {
+        let mut storage = GovernanceStorage::default();
+        storage.set_new_governance(Governance::default());
+        let base_version = storage.get_version();
+
+        assert!(storage.apply_patch(&add_participant(1), base_version, |_| Ok(())).is_ok());
+        assert!(storage.apply_patch(&add_participant(2), base_version, |_| Ok(())).is_ok());
+        assert_eq!(storage.get_governance().participants.len(), 2);
+    }
+
+    #[test]
+    fn test_conflicting_patches_of_same_participant() {
+        let mut storage = GovernanceStorage::default();
+        storage.set_new_governance(Governance::default());
+        let base_version = storage.get_version();
+
+        assert!(storage.apply_patch(&add_participant(1), base_version, |_| Ok(())).is_ok());
+        let remove = GovernancePatch::RemoveParticipant {
+            participant: Principal::from_slice(&[1]),
+        };
+        assert!(storage.apply_patch(&remove, base_version, |_| Ok(())).is_err());
+        assert!(storage.apply_patch(&remove, storage.get_version(), |_| Ok(())).is_ok());
+    }
+
+    #[test]
+    fn test_permission_resolved_through_role() {
+        let mut storage = GovernanceStorage::default();
+        storage.set_new_governance(Governance {
+            roles: Some(vec![GovernanceRole {
+                name: "voter".to_string(),
+                proposal_permissions: vec![(ProposalType::UpgradeCanister, vec![ProposalPermission::Vote])],
+                canister_scope: None,
+            }]),
+            ..Default::default()
+        });
+        assert!(storage
+            .apply_patch(&add_participant(1), storage.get_version(), |_| Ok(()))
+            .is_ok());
+
+        let participant = Principal::from_slice(&[1]);
+        let vote = ProposalPermission::Vote;
+        assert!(!storage.check_is_permission(&participant, &ProposalType::UpgradeCanister, None, &vote));
+
+        let change_roles = GovernancePatch::ChangeRoles {
+            participant,
+            roles: vec!["voter".to_string()],
+        };
+        assert!(storage.apply_patch(&change_roles, storage.get_version(), |_| Ok(())).is_ok());
+        assert!(storage.check_is_permission(&participant, &ProposalType::UpgradeCanister, None, &vote));
+        assert!(!storage.check_is_permission(&participant, &ProposalType::CallCanister, None, &vote));
+    }
+
+    #[test]
+    fn test_scoped_role_and_voting_config() {
+        let canister_id = Principal::from_slice(&[10]);
+        let other_canister_id = Principal::from_slice(&[11]);
+        let config = |count| VotingConfig {
+            stop_vote_count: count,
+            positive_vote_count: count,
+        };
+
+        let mut storage = GovernanceStorage::default();
+        storage.set_new_governance(Governance {
+            voting_configuration: vec![(ProposalType::UpgradeCanister, config(1))],
+            roles: Some(vec![GovernanceRole {
+                name: "upgrader".to_string(),
+                proposal_permissions: vec![(ProposalType::UpgradeCanister, vec![ProposalPermission::Add])],
+                canister_scope: Some(vec![CanisterScope::Group {
+                    name: "frontend".to_string(),
+                }]),
+            }]),
+            canister_groups: Some(vec![CanisterGroup {
+                name: "frontend".to_string(),
+                canister_ids: vec![canister_id],
+            }]),
+            scoped_voting_configuration: Some(vec![ScopedVotingConfig {
+                scope: CanisterScope::Canister { canister_id },
+                proposal_type: ProposalType::UpgradeCanister,
+                voting_config: config(2),
+            }]),
+            ..Default::default()
+        });
+
+        let participant = Principal::from_slice(&[1]);
+        let change_roles = GovernancePatch::ChangeRoles {
+            participant,
+            roles: vec!["upgrader".to_string()],
+        };
+        assert!(storage
+            .apply_patch(&add_participant(1), storage.get_version(), |_| Ok(()))
+            .is_ok());
+        assert!(storage.apply_patch(&change_roles, storage.get_version(), |_| Ok(())).is_ok());
+
+        let add = ProposalPermission::Add;
+        let upgrade = ProposalType::UpgradeCanister;
+        assert!(storage.check_is_permission(&participant, &upgrade, Some(&canister_id), &add));
+        assert!(!storage.check_is_permission(&participant, &upgrade, Some(&other_canister_id), &add));
+        assert!(!storage.check_is_permission(&participant, &upgrade, None, &add));
+
+        assert_eq!(
+            storage.get_voting_configuration(&upgrade, Some(&canister_id)),
+            Some(&config(2))
+        );
+        assert_eq!(
+            storage.get_voting_configuration(&upgrade, Some(&other_canister_id)),
+            Some(&config(1))
+        );
+    }
+
+    #[test]
+    fn test_call_policy() {
+        let canister_id = Principal::from_slice(&[10]);
+        let mut storage = GovernanceStorage::default();
+        storage.set_new_governance(Governance {
+            call_policy: Some(CallPolicy {
+                rules: vec![CanisterCallRule {
+                    canister_id,
+                    allowed_methods: Some(vec!["set_config".to_string()]),
+                    max_payment: Some(100),
+                }],
+                denied_methods: vec![DeniedMethod {
+                    canister_id: Some(Principal::management_canister()),
+                    method: "uninstall_code".to_string(),
+                }],
+            }),
+            ..Default::default()
+        });
+
+        let call = |canister_id: Principal, method: &str, payment: Option<u64>| CallCanister {
+            canister_id,
+            method: method.to_string(),
+            argument_candid: "()".to_string(),
+            payment,
+            canister_did: None,
+        };
+
+        assert!(storage.check_call_policy(&call(canister_id, "set_config", Some(100))).is_ok());
+        assert!(storage
+            .check_call_policy(&call(canister_id, "set_config", Some(101)))
+            .is_err());
+        assert!(storage.check_call_policy(&call(canister_id, "reset", None)).is_err());
+        assert!(storage
+            .check_call_policy(&call(Principal::management_canister(), "uninstall_code", None))
+            .is_err());
+        assert!(storage
+            .check_call_policy(&call(Principal::management_canister(), "canister_status", None))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_self_targeting_config_migration() {
+        let config = VotingConfig {
+            stop_vote_count: 2,
+            positive_vote_count: 2,
+        };
+
+        let mut storage = GovernanceStorage::default();
+        storage.set_new_governance(Governance {
+            voting_configuration: vec![(ProposalType::UpdateGovernance, config.clone())],
+            ..Default::default()
+        });
+
+        assert_eq!(storage.migrate_self_targeting_voting_config(), Some(&config));
+        assert_eq!(storage.get_governance().self_targeting_voting_config, Some(config));
+        assert_eq!(storage.migrate_self_targeting_voting_config(), None);
+    }
+}
@@ -1,15 +1,39 @@
+use crate::model::canister_did::CanisterDidStorage;
+use crate::model::canister_status::CanisterStatusStorage;
+use crate::model::freeze::FreezeStorage;
 use crate::model::geek_users::GeekUserStorage;
 use crate::model::governance::GovernanceStorage;
+use crate::model::module_hashes::ApprovedModuleHashStorage;
+use crate::model::principal_rotation::PrincipalRotationStorage;
 use crate::model::proposal::ProposalStorage;
+use crate::model::vote_delegation::VoteDelegationStorage;
 use serde::{Deserialize, Serialize};
 
+pub mod canister_did;
+pub mod canister_status;
+pub mod freeze;
 pub mod geek_users;
 pub mod governance;
+pub mod module_hashes;
+pub mod principal_rotation;
 pub mod proposal;
+pub mod vote_delegation;
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct DataModel {
     pub geek_user_storage: GeekUserStorage,
     pub proposal_storage: ProposalStorage,
     pub governance_storage: GovernanceStorage,
+    #[serde(default)]
+    pub canister_status_storage: CanisterStatusStorage,
+    #[serde(default)]
+    pub approved_module_hash_storage: ApprovedModuleHashStorage,
+    #[serde(default)]
+    pub canister_did_storage: CanisterDidStorage,
+    #[serde(default)]
+    pub vote_delegation_storage: VoteDelegationStorage,
+    #[serde(default)]
+    pub principal_rotation_storage: PrincipalRotationStorage,
+    #[serde(default)]
+    pub freeze_storage: FreezeStorage,
 }
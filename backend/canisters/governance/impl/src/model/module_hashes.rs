@@ -0,0 +1,36 @@
+use candid::Principal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct ApprovedModuleHashStorage {
+    hashes: BTreeMap<Principal, BTreeSet<String>>,
+}
+
+impl ApprovedModuleHashStorage {
+    pub(crate) fn update_hashes(&mut self, canister_id: Principal, added: &[String], removed: &[String]) {
+        let hashes = self.hashes.entry(canister_id).or_default();
+        hashes.extend(added.iter().cloned());
+        removed.iter().for_each(|hash| {
+            hashes.remove(hash);
+        });
+
+        if hashes.is_empty() {
+            self.hashes.remove(&canister_id);
+        }
+    }
+
+    pub(crate) fn is_approved(&self, canister_id: &Principal, module_hash: &String) -> bool {
+        self.hashes
+            .get(canister_id)
+            .map(|hashes| hashes.contains(module_hash))
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn get_hashes(&self) -> Vec<(Principal, Vec<String>)> {
+        self.hashes
+            .iter()
+            .map(|(canister_id, hashes)| (*canister_id, hashes.iter().cloned().collect()))
+            .collect()
+    }
+}
@@ -0,0 +1,183 @@
+use candid::Principal;
+use governance_canister::types::{PrincipalRotation, TimestampMillis};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const MAX_COMPLETED_ROTATIONS: usize = 100;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct PrincipalRotationStorage {
+    pending: BTreeMap<Principal, PrincipalRotation>,
+    completed: Vec<PrincipalRotation>,
+}
+
+impl PrincipalRotationStorage {
+    /// Replaces the pending rotation of the participant.
+    pub(crate) fn set_pending(&mut self, rotation: PrincipalRotation) {
+        self.pending.insert(rotation.participant, rotation);
+    }
+
+    pub(crate) fn get_pending_mut(&mut self, participant: &Principal) -> Option<&mut PrincipalRotation> {
+        self.pending.get_mut(participant)
+    }
+
+    pub(crate) fn remove_pending(&mut self, participant: &Principal) -> Option<PrincipalRotation> {
+        self.pending.remove(participant)
+    }
+
+    pub(crate) fn is_pending_new_principal(&self, principal: &Principal) -> bool {
+        self.pending.values().any(|rotation| &rotation.new_principal == principal)
+    }
+
+    /// Rotations confirmed at least `delay` ago.
+    pub(crate) fn get_due_rotations(&self, time: TimestampMillis, delay: u64) -> Vec<PrincipalRotation> {
+        self.pending
+            .values()
+            .filter(|rotation| {
+                rotation
+                    .confirmed
+                    .is_some_and(|confirmed| confirmed.saturating_add(delay) <= time)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Only the latest completed rotations are kept in the history.
+    pub(crate) fn complete(&mut self, participant: &Principal, time: TimestampMillis) {
+        if let Some(mut rotation) = self.pending.remove(participant) {
+            rotation.completed = Some(time);
+            self.completed.push(rotation);
+
+            let excess = self.completed.len().saturating_sub(MAX_COMPLETED_ROTATIONS);
+            self.completed.drain(..excess);
+        }
+    }
+
+    pub(crate) fn get_pending(&self) -> Vec<PrincipalRotation> {
+        self.pending.values().cloned().collect()
+    }
+
+    pub(crate) fn get_completed(&self) -> &[PrincipalRotation] {
+        &self.completed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::governance::GovernanceStorage;
+    use crate::model::principal_rotation::{PrincipalRotationStorage, MAX_COMPLETED_ROTATIONS};
+    use candid::Principal;
+    use governance_canister::types::{Governance, GovernanceParticipant, GuardianConfig, PrincipalRotation};
+
+    const DELAY: u64 = 1_000;
+
+    fn principal(id: u8) -> Principal {
+        Principal::from_slice(&[id])
+    }
+
+    fn rotation(participant: u8, new_principal: u8) -> PrincipalRotation {
+        PrincipalRotation {
+            participant: principal(participant),
+            new_principal: principal(new_principal),
+            proposed: 0,
+            confirmed: None,
+            completed: None,
+        }
+    }
+
+    #[test]
+    fn test_rotation_completes_after_delay() {
+        let mut storage = PrincipalRotationStorage::default();
+        storage.set_pending(rotation(1, 2));
+        assert!(storage.is_pending_new_principal(&principal(2)));
+
+        // not confirmed rotations never become due
+        assert!(storage.get_due_rotations(2 * DELAY, DELAY).is_empty());
+
+        storage.get_pending_mut(&principal(1)).unwrap().confirmed = Some(100);
+        assert!(storage.get_due_rotations(100 + DELAY - 1, DELAY).is_empty());
+        assert_eq!(storage.get_due_rotations(100 + DELAY, DELAY).len(), 1);
+
+        storage.complete(&principal(1), 100 + DELAY);
+        assert!(storage.get_pending().is_empty());
+        assert_eq!(storage.get_completed().len(), 1);
+        assert_eq!(storage.get_completed()[0].completed, Some(100 + DELAY));
+    }
+
+    #[test]
+    fn test_vetoed_rotation_is_not_completed() {
+        let mut storage = PrincipalRotationStorage::default();
+        storage.set_pending(rotation(1, 2));
+        storage.get_pending_mut(&principal(1)).unwrap().confirmed = Some(0);
+
+        assert!(storage.remove_pending(&principal(1)).is_some());
+        assert!(storage.get_due_rotations(DELAY, DELAY).is_empty());
+
+        storage.complete(&principal(1), DELAY);
+        assert!(storage.get_completed().is_empty());
+    }
+
+    #[test]
+    fn test_completed_history_is_bounded() {
+        let mut storage = PrincipalRotationStorage::default();
+        for id in 0..=MAX_COMPLETED_ROTATIONS as u8 {
+            storage.set_pending(rotation(id, id + 1));
+            storage.complete(&principal(id), id as u64);
+        }
+
+        assert_eq!(storage.get_completed().len(), MAX_COMPLETED_ROTATIONS);
+        assert_eq!(storage.get_completed()[0].participant, principal(1));
+    }
+
+    #[test]
+    fn test_rotate_participant_bumps_governance_version() {
+        let mut governance_storage = GovernanceStorage::default();
+        governance_storage.set_new_governance(Governance {
+            participants: vec![(
+                principal(1),
+                GovernanceParticipant {
+                    name: "participant".to_string(),
+                    proposal_permissions: vec![],
+                    roles: None,
+                    vote_public_key: None,
+                },
+            )],
+            ..Default::default()
+        });
+        let version = governance_storage.get_version();
+
+        assert!(governance_storage.rotate_participant(&principal(1), principal(2)).is_ok());
+        assert_eq!(governance_storage.get_version(), version + 1);
+        assert!(governance_storage.get_governance_participant(&principal(1)).is_none());
+        assert!(governance_storage.get_governance_participant(&principal(2)).is_some());
+
+        assert!(governance_storage.rotate_participant(&principal(3), principal(4)).is_err());
+    }
+
+    #[test]
+    fn test_rotate_guardian() {
+        let mut governance_storage = GovernanceStorage::default();
+        governance_storage.set_new_governance(Governance {
+            participants: vec![(
+                principal(1),
+                GovernanceParticipant {
+                    name: "guardian".to_string(),
+                    proposal_permissions: vec![],
+                    roles: None,
+                    vote_public_key: None,
+                },
+            )],
+            guardians: Some(GuardianConfig {
+                guardians: vec![principal(1), principal(5)],
+                max_freeze_duration_millis: DELAY,
+                unfreeze_quorum: 1,
+            }),
+            ..Default::default()
+        });
+
+        assert!(governance_storage.rotate_participant(&principal(1), principal(2)).is_ok());
+
+        let guardians = &governance_storage.get_governance().guardians.as_ref().unwrap().guardians;
+        assert_eq!(guardians, &vec![principal(2), principal(5)]);
+    }
+}
@@ -1,4 +1,8 @@
-use governance_canister::types::{Proposal, ProposalId};
+use crate::model::governance::GovernanceStorage;
+use candid::Principal;
+use governance_canister::types::{
+    Proposal, ProposalId, ProposalPermission, ProposalState, ProposalType, StaleProposalPolicy, TimestampMillis, VotingConfig,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::btree_map::Iter;
 use std::collections::BTreeMap;
@@ -37,4 +41,199 @@ impl ProposalStorage {
     pub(crate) fn get_proposals_iter(&self) -> Iter<'_, ProposalId, Proposal> {
         self.proposals_table.iter()
     }
+
+    /// Keeps proposals and votes of the participant linked to its new principal.
+    pub(crate) fn rotate_principal(&mut self, old_principal: &Principal, new_principal: Principal) {
+        let rotate = |principal: &mut Principal| {
+            if principal == old_principal {
+                *principal = new_principal;
+            }
+        };
+
+        for proposal in self.proposals_table.values_mut() {
+            rotate(&mut proposal.initiator);
+            for vote in proposal.voting.votes.iter_mut() {
+                rotate(&mut vote.participant);
+                if let Some(on_behalf_of) = vote.on_behalf_of.as_mut() {
+                    rotate(on_behalf_of);
+                }
+            }
+        }
+    }
+
+    /// Applies the stale proposal policy to not performed proposals created or last re-evaluated
+    /// under an older governance version. The creation version stays the base of a governance patch.
+    pub(crate) fn apply_stale_proposal_policy(&mut self, governance_storage: &GovernanceStorage, time: TimestampMillis) {
+        let policy = governance_storage
+            .get_governance()
+            .stale_proposal_policy
+            .clone()
+            .unwrap_or(StaleProposalPolicy::Keep);
+
+        if policy == StaleProposalPolicy::Keep {
+            return;
+        }
+
+        let governance_version = governance_storage.get_version();
+
+        for proposal in self.proposals_table.values_mut() {
+            if !matches!(proposal.state, ProposalState::Voting | ProposalState::Approved)
+                || proposal
+                    .reevaluated_version
+                    .or(proposal.governance_version)
+                    .unwrap_or_default()
+                    >= governance_version
+            {
+                continue;
+            }
+
+            let proposal_type = ProposalType::from(&proposal.detail);
+            let target_canister = proposal.detail.get_target_canister();
+            let voting_config = governance_storage.get_proposal_voting_configuration(proposal);
+
+            match (&policy, voting_config) {
+                (StaleProposalPolicy::Reevaluate, Some(voting_config)) => {
+                    proposal.voting.votes.retain(|vote| {
+                        governance_storage.check_is_permission(
+                            &vote.get_voter(),
+                            &proposal_type,
+                            target_canister.as_ref(),
+                            &ProposalPermission::Vote,
+                        )
+                    });
+                    proposal.state = ProposalState::Voting;
+                    proposal.reevaluated_version = Some(governance_version);
+                    check_voting_finish(proposal, voting_config);
+                }
+                _ => {
+                    proposal.state = ProposalState::Invalidated { governance_version };
+                }
+            }
+
+            proposal.updated = time;
+        }
+    }
+}
+
+pub(crate) fn check_voting_finish(proposal: &mut Proposal, voting_config: &VotingConfig) {
+    if (proposal.voting.votes.len() as u32) < voting_config.stop_vote_count {
+        return;
+    }
+
+    let mut positive = 0;
+
+    for vote in proposal.voting.votes.iter() {
+        if vote.vote {
+            positive += 1;
+        }
+    }
+
+    proposal.state = if positive >= voting_config.positive_vote_count {
+        ProposalState::Approved
+    } else {
+        ProposalState::Declined
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::governance::GovernanceStorage;
+    use crate::model::proposal::ProposalStorage;
+    use candid::Principal;
+    use governance_canister::types::{
+        Governance, GovernanceParticipant, GovernancePatch, Proposal, ProposalDetail, ProposalPermission, ProposalState,
+        ProposalType, StaleProposalPolicy, Vote, Voting, VotingConfig,
+    };
+
+    fn governance(stop_vote_count: u32, stale_proposal_policy: StaleProposalPolicy) -> Governance {
+        Governance {
+            participants: vec![(
+                Principal::from_slice(&[1]),
+                GovernanceParticipant {
+                    name: "voter".to_string(),
+                    proposal_permissions: vec![(ProposalType::UpdateGovernance, vec![ProposalPermission::Vote])],
+                    roles: None,
+                    vote_public_key: None,
+                },
+            )],
+            voting_configuration: vec![(
+                ProposalType::UpdateGovernance,
+                VotingConfig {
+                    stop_vote_count,
+                    positive_vote_count: stop_vote_count,
+                },
+            )],
+            stale_proposal_policy: Some(stale_proposal_policy),
+            ..Default::default()
+        }
+    }
+
+    fn storage_with_approved_proposal(governance_storage: &GovernanceStorage) -> ProposalStorage {
+        let mut storage = ProposalStorage::default();
+        let proposal_id = storage.get_new_proposal_id();
+        storage.add_new_proposal(
+            proposal_id,
+            Proposal {
+                proposal_id,
+                created: 0,
+                initiator: Principal::anonymous(),
+                description: None,
+                detail: ProposalDetail::PatchGovernance {
+                    patch: GovernancePatch::RemoveParticipant {
+                        participant: Principal::anonymous(),
+                    },
+                },
+                updated: 0,
+                state: ProposalState::Approved,
+                voting: Voting {
+                    votes: vec![Vote {
+                        participant: Principal::from_slice(&[1]),
+                        vote_time: 0,
+                        vote: true,
+                        on_behalf_of: None,
+                    }],
+                },
+                governance_version: Some(governance_storage.get_version()),
+                reevaluated_version: None,
+                self_targeting: None,
+            },
+        );
+        storage
+    }
+
+    #[test]
+    fn test_reevaluate_stale_proposal() {
+        let mut governance_storage = GovernanceStorage::default();
+        governance_storage.set_new_governance(governance(1, StaleProposalPolicy::Reevaluate));
+        let mut storage = storage_with_approved_proposal(&governance_storage);
+
+        let base_version = governance_storage.get_version();
+
+        governance_storage.set_new_governance(governance(2, StaleProposalPolicy::Reevaluate));
+        storage.apply_stale_proposal_policy(&governance_storage, 1);
+
+        let proposal = storage.get_proposal(&1).unwrap();
+        assert!(matches!(proposal.state, ProposalState::Voting));
+        assert_eq!(proposal.governance_version, Some(base_version));
+        assert_eq!(proposal.reevaluated_version, Some(governance_storage.get_version()));
+
+        // an already re-evaluated proposal is not re-evaluated again under the same version
+        storage.apply_stale_proposal_policy(&governance_storage, 2);
+        assert_eq!(storage.get_proposal(&1).unwrap().updated, 1);
+    }
+
+    #[test]
+    fn test_invalidate_stale_proposal() {
+        let mut governance_storage = GovernanceStorage::default();
+        governance_storage.set_new_governance(governance(1, StaleProposalPolicy::Invalidate));
+        let mut storage = storage_with_approved_proposal(&governance_storage);
+
+        governance_storage.set_new_governance(governance(1, StaleProposalPolicy::Invalidate));
+        storage.apply_stale_proposal_policy(&governance_storage, 1);
+
+        assert!(matches!(
+            storage.get_proposal(&1).unwrap().state,
+            ProposalState::Invalidated { governance_version: 2 }
+        ));
+    }
 }
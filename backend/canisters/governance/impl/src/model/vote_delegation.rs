@@ -0,0 +1,161 @@
+use candid::Principal;
+use governance_canister::types::{ProposalType, TimestampMillis, VoteDelegation};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct VoteDelegationStorage {
+    delegations: BTreeMap<Principal, VoteDelegation>,
+}
+
+impl VoteDelegationStorage {
+    pub(crate) fn set_delegation(&mut self, delegator: Principal, delegation: Option<VoteDelegation>) {
+        match delegation {
+            Some(delegation) => {
+                self.delegations.insert(delegator, delegation);
+            }
+            None => {
+                self.delegations.remove(&delegator);
+            }
+        }
+    }
+
+    /// Participants whose not expired delegation for the proposal type is held by the delegate.
+    pub(crate) fn get_delegators(
+        &self,
+        delegate: &Principal,
+        proposal_type: &ProposalType,
+        time: TimestampMillis,
+    ) -> Vec<Principal> {
+        self.delegations
+            .iter()
+            .filter(|(_, delegation)| {
+                &delegation.delegate == delegate
+                    && delegation.expiration > time
+                    && delegation
+                        .proposal_types
+                        .as_ref()
+                        .is_none_or(|proposal_types| proposal_types.contains(proposal_type))
+            })
+            .map(|(delegator, _)| *delegator)
+            .collect()
+    }
+
+    pub(crate) fn rotate_principal(&mut self, old_principal: &Principal, new_principal: Principal) {
+        if let Some(delegation) = self.delegations.remove(old_principal) {
+            self.delegations.insert(new_principal, delegation);
+        }
+
+        self.delegations
+            .values_mut()
+            .filter(|delegation| &delegation.delegate == old_principal)
+            .for_each(|delegation| delegation.delegate = new_principal);
+    }
+
+    pub(crate) fn get_delegations(&self) -> Vec<(Principal, VoteDelegation)> {
+        self.delegations
+            .iter()
+            .map(|(delegator, delegation)| (*delegator, delegation.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::proposal::check_voting_finish;
+    use crate::model::vote_delegation::VoteDelegationStorage;
+    use candid::Principal;
+    use governance_canister::types::{
+        CallCanister, Proposal, ProposalDetail, ProposalState, ProposalType, Vote, VoteDelegation, Voting, VotingConfig,
+    };
+
+    const EXPIRATION: u64 = 1_000;
+
+    fn principal(id: u8) -> Principal {
+        Principal::from_slice(&[id])
+    }
+
+    fn delegation(delegate: u8, proposal_types: Option<Vec<ProposalType>>) -> Option<VoteDelegation> {
+        Some(VoteDelegation {
+            delegate: principal(delegate),
+            proposal_types,
+            expiration: EXPIRATION,
+        })
+    }
+
+    fn vote(participant: Principal, on_behalf_of: Option<Principal>) -> Vote {
+        Vote {
+            participant,
+            vote_time: 0,
+            vote: true,
+            on_behalf_of,
+        }
+    }
+
+    #[test]
+    fn test_get_delegators() {
+        let mut storage = VoteDelegationStorage::default();
+        storage.set_delegation(principal(1), delegation(10, None));
+        storage.set_delegation(principal(2), delegation(10, Some(vec![ProposalType::CallCanister])));
+        storage.set_delegation(principal(3), delegation(11, None));
+
+        assert_eq!(
+            storage.get_delegators(&principal(10), &ProposalType::CallCanister, 0),
+            vec![principal(1), principal(2)]
+        );
+        assert_eq!(
+            storage.get_delegators(&principal(10), &ProposalType::UpgradeCanister, 0),
+            vec![principal(1)]
+        );
+        assert!(storage
+            .get_delegators(&principal(10), &ProposalType::CallCanister, EXPIRATION)
+            .is_empty());
+
+        storage.set_delegation(principal(1), None);
+        assert!(storage
+            .get_delegators(&principal(10), &ProposalType::UpgradeCanister, 0)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_delegated_vote_reaches_stop_vote_count() {
+        let mut storage = VoteDelegationStorage::default();
+        storage.set_delegation(principal(1), delegation(10, None));
+
+        let mut proposal = Proposal {
+            proposal_id: 1,
+            created: 0,
+            initiator: principal(10),
+            description: None,
+            detail: ProposalDetail::CallCanister {
+                task: CallCanister {
+                    canister_id: principal(20),
+                    method: "method".to_string(),
+                    argument_candid: "()".to_string(),
+                    canister_did: None,
+                    payment: None,
+                },
+            },
+            updated: 0,
+            state: ProposalState::Voting,
+            voting: Voting::default(),
+            governance_version: None,
+            reevaluated_version: None,
+            self_targeting: None,
+        };
+        let voting_config = VotingConfig {
+            stop_vote_count: 2,
+            positive_vote_count: 2,
+        };
+
+        proposal.voting.votes.push(vote(principal(10), None));
+        check_voting_finish(&mut proposal, &voting_config);
+        assert!(matches!(proposal.state, ProposalState::Voting));
+
+        for delegator in storage.get_delegators(&principal(10), &ProposalType::CallCanister, 0) {
+            proposal.voting.votes.push(vote(principal(10), Some(delegator)));
+        }
+        check_voting_finish(&mut proposal, &voting_config);
+        assert!(matches!(proposal.state, ProposalState::Approved));
+    }
+}
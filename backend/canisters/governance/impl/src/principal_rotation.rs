@@ -0,0 +1,57 @@
+use crate::time::get_unix_epoch_time_millis;
+use crate::{log_error, log_info, mutate_state};
+use governance_canister::types::Governance;
+use std::time::Duration;
+
+const DEFAULT_ROTATION_DELAY_MILLIS: u64 = 3 * 24 * 60 * 60 * 1000;
+const COMPLETION_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+pub(crate) fn start_rotation_timer() {
+    ic_cdk_timers::set_timer_interval(COMPLETION_INTERVAL, complete_due_rotations);
+}
+
+pub(crate) fn get_rotation_delay(governance: &Governance) -> u64 {
+    governance
+        .principal_rotation_delay_millis
+        .unwrap_or(DEFAULT_ROTATION_DELAY_MILLIS)
+}
+
+/// Rotations that were not vetoed during the delay after confirmation take effect.
+async fn complete_due_rotations() {
+    mutate_state(|state| {
+        let time = get_unix_epoch_time_millis();
+        let delay = get_rotation_delay(state.model.governance_storage.get_governance());
+        let due_rotations = state.model.principal_rotation_storage.get_due_rotations(time, delay);
+
+        if due_rotations.is_empty() {
+            return;
+        }
+
+        for rotation in due_rotations {
+            let participant = rotation.participant;
+            let new_principal = rotation.new_principal;
+
+            match state.model.governance_storage.rotate_participant(&participant, new_principal) {
+                Ok(_) => {
+                    state.model.proposal_storage.rotate_principal(&participant, new_principal);
+                    state
+                        .model
+                        .vote_delegation_storage
+                        .rotate_principal(&participant, new_principal);
+                    state.model.freeze_storage.rotate_principal(&participant, new_principal);
+                    state.model.principal_rotation_storage.complete(&participant, time);
+                    log_info!("Participant '{participant}' is rotated to '{new_principal}'");
+                }
+                Err(reason) => {
+                    state.model.principal_rotation_storage.remove_pending(&participant);
+                    log_error!("Can not rotate participant '{participant}' to '{new_principal}': {reason}");
+                }
+            }
+        }
+
+        state
+            .model
+            .proposal_storage
+            .apply_stale_proposal_policy(&state.model.governance_storage, time);
+    });
+}
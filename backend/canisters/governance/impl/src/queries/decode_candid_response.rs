@@ -1,9 +1,8 @@
 use candid_parser::parse_idl_args;
+use canister_did::decode_method_response;
 use governance_canister::decode_candid_response::*;
 use ic_cdk_macros::query;
 
-use crate::updates::perform_proposal::decode_method_response;
-
 #[query]
 fn decode_candid_response(
     Args {
@@ -0,0 +1,13 @@
+use crate::guards::caller_is_governance_user;
+use crate::read_state;
+use governance_canister::get_approved_module_hashes::*;
+use ic_cdk_macros::query;
+
+#[query(guard = "caller_is_governance_user")]
+fn get_approved_module_hashes(_args: Args) -> Response {
+    read_state(|state| {
+        Response::Ok(GetApprovedModuleHashesResult {
+            module_hashes: state.model.approved_module_hash_storage.get_hashes(),
+        })
+    })
+}
@@ -8,6 +8,7 @@ fn get_governance(_args: Args) -> Response {
     read_state(|state| {
         Response::Ok(GetGovernanceResult {
             governance: state.model.governance_storage.get_governance().clone(),
+            governance_version: state.model.governance_storage.get_version(),
         })
     })
 }
@@ -0,0 +1,18 @@
+use crate::guards::caller_is_authorised;
+use crate::read_state;
+use crate::time::get_unix_epoch_time_millis;
+use governance_canister::get_governance_freeze::*;
+use ic_cdk_macros::query;
+
+#[query(guard = "caller_is_authorised")]
+fn get_governance_freeze(_args: Args) -> Response {
+    read_state(|state| {
+        Response::Ok(GetGovernanceFreezeResult {
+            freeze: state
+                .model
+                .freeze_storage
+                .get_active_freeze(get_unix_epoch_time_millis())
+                .cloned(),
+        })
+    })
+}
@@ -0,0 +1,29 @@
+use crate::guards::caller_is_governance_user;
+use crate::read_state;
+use governance_canister::get_managed_canister_statuses::*;
+use governance_canister::types::ManagedCanisterStatus;
+use ic_cdk_macros::query;
+
+#[query(guard = "caller_is_governance_user")]
+fn get_managed_canister_statuses(_args: Args) -> Response {
+    read_state(|state| {
+        let statuses = state
+            .model
+            .governance_storage
+            .get_managed_canisters()
+            .iter()
+            .map(|canister| {
+                let entry = state.model.canister_status_storage.get_entry(&canister.canister_id);
+                ManagedCanisterStatus {
+                    canister_id: canister.canister_id,
+                    name: canister.name.clone(),
+                    uploader_id: canister.uploader_id,
+                    status: entry.and_then(|entry| entry.status.clone()),
+                    error: entry.and_then(|entry| entry.error.clone()),
+                }
+            })
+            .collect();
+
+        Response::Ok(GetManagedCanisterStatusesResult { statuses })
+    })
+}
@@ -0,0 +1,14 @@
+use crate::guards::caller_is_governance_user;
+use crate::read_state;
+use governance_canister::get_principal_rotations::*;
+use ic_cdk_macros::query;
+
+#[query(guard = "caller_is_governance_user")]
+fn get_principal_rotations(_args: Args) -> Response {
+    read_state(|state| {
+        Response::Ok(GetPrincipalRotationsResult {
+            pending: state.model.principal_rotation_storage.get_pending(),
+            completed: state.model.principal_rotation_storage.get_completed().to_vec(),
+        })
+    })
+}
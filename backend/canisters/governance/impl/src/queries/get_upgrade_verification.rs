@@ -0,0 +1,64 @@
+use crate::guards::caller_is_governance_user;
+use crate::read_state;
+use candid::Principal;
+use governance_canister::get_upgrade_verification::*;
+use governance_canister::types::ProposalDetail;
+use ic_cdk::call::CallResult;
+use ic_cdk_macros::query;
+use uploader_canister::get_operation_status::GetOperationStatusResponse;
+use uploader_canister::types::EmptyArgs;
+
+#[query(composite = true, guard = "caller_is_governance_user")]
+async fn get_upgrade_verification(args: Args) -> Response {
+    match get_upgrade_verification_int(args).await {
+        Ok(result) => Response::Ok(result),
+        Err(error) => Response::Err(error),
+    }
+}
+
+async fn get_upgrade_verification_int(
+    args: GetUpgradeVerificationArgs,
+) -> Result<GetUpgradeVerificationResult, GetUpgradeVerificationError> {
+    let task = read_state(|state| {
+        let proposal = state
+            .model
+            .proposal_storage
+            .get_proposal(&args.proposal_id)
+            .ok_or(GetUpgradeVerificationError::ProposalNotFound)?;
+
+        match &proposal.detail {
+            ProposalDetail::UpgradeCanister { task } => Ok(task.clone()),
+            _ => Err(GetUpgradeVerificationError::NotUpgradeProposal),
+        }
+    })?;
+
+    let GetOperationStatusResponse::Ok(status) =
+        call_get_operation_status(task.uploader_id)
+            .await
+            .map_err(|error| GetUpgradeVerificationError::CallError {
+                reason: format!("error while perform uploader canister call: {error:?}"),
+            })?;
+
+    let uploaded_wasm = status
+        .grant
+        .filter(|grant| grant.canister_id == task.canister_id && grant.operator == task.operator_id)
+        .and(status.uploaded_wasm)
+        .map(|uploaded_wasm| UploadedWasmVerification {
+            wasm_length: uploaded_wasm.wasm_length as u64,
+            hash_matches: uploaded_wasm.wasm_hash == task.module_hash,
+            wasm_hash: uploaded_wasm.wasm_hash,
+        });
+
+    Ok(GetUpgradeVerificationResult {
+        module_hash: task.module_hash,
+        source: task.source,
+        uploaded_wasm,
+    })
+}
+
+async fn call_get_operation_status(uploader_canister: Principal) -> CallResult<GetOperationStatusResponse> {
+    Ok(ic_cdk::call::Call::bounded_wait(uploader_canister, "get_operation_status")
+        .with_arg(EmptyArgs {})
+        .await?
+        .candid()?)
+}
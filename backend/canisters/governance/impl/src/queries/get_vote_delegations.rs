@@ -0,0 +1,13 @@
+use crate::guards::caller_is_governance_user;
+use crate::read_state;
+use governance_canister::get_vote_delegations::*;
+use ic_cdk_macros::query;
+
+#[query(guard = "caller_is_governance_user")]
+fn get_vote_delegations(_args: Args) -> Response {
+    read_state(|state| {
+        Response::Ok(GetVoteDelegationsResult {
+            delegations: state.model.vote_delegation_storage.get_delegations(),
+        })
+    })
+}
@@ -1,9 +1,16 @@
 pub mod decode_candid_response;
 pub mod encode_candid_args;
+pub mod get_approved_module_hashes;
 pub mod get_canistergeek_information;
 pub mod get_geek_user_principals;
 pub mod get_governance;
+pub mod get_governance_freeze;
+pub mod get_managed_canister_statuses;
 pub mod get_my_governance_participant;
+pub mod get_principal_rotations;
 pub mod get_proposal;
 pub mod get_proposals;
+pub mod get_upgrade_verification;
+pub mod get_vote_delegations;
 pub mod http_request;
+pub mod simulate_proposal;
@@ -0,0 +1,204 @@
+use crate::guards::caller_is_governance_user;
+use crate::model::governance::patch_governance;
+use crate::read_state;
+use crate::updates::add_new_proposal::parse_call_argument;
+use crate::updates::perform_proposal::decode_call_response;
+use candid::types::FuncMode;
+use canister_did::parse_canister_did;
+use governance_canister::simulate_proposal::*;
+use governance_canister::types::{CallCanister, Governance, PerformResult, ProposalDetail};
+use ic_cdk_macros::query;
+use std::collections::BTreeSet;
+
+/// A composite query, so the replica allows only query calls and drops any state change they make.
+#[query(composite = true, guard = "caller_is_governance_user")]
+async fn simulate_proposal(args: Args) -> Response {
+    match simulate_proposal_int(args).await {
+        Ok(result) => Response::Ok(result),
+        Err(error) => Response::Err(error),
+    }
+}
+
+async fn simulate_proposal_int(args: SimulateProposalArgs) -> Result<SimulateProposalResult, SimulateProposalError> {
+    let (proposal_detail, current_governance) = read_state(|state| {
+        state
+            .model
+            .proposal_storage
+            .get_proposal(&args.proposal_id)
+            .map(|proposal| {
+                (
+                    proposal.detail.clone(),
+                    state.model.governance_storage.get_governance().clone(),
+                )
+            })
+            .ok_or(SimulateProposalError::ProposalNotFound)
+    })?;
+
+    match proposal_detail {
+        ProposalDetail::UpdateGovernance { new_governance } => Ok(SimulateProposalResult::GovernanceDiff {
+            diff: get_governance_diff(&current_governance, &new_governance),
+        }),
+        ProposalDetail::PatchGovernance { patch } => {
+            let new_governance = patch_governance(&current_governance, &patch)
+                .map_err(|reason| SimulateProposalError::NotSimulatable { reason })?;
+            Ok(SimulateProposalResult::GovernanceDiff {
+                diff: get_governance_diff(&current_governance, &new_governance),
+            })
+        }
+        ProposalDetail::CallCanister { task } => {
+            let result = simulate_query_call(&task)
+                .await
+                .map_err(|reason| SimulateProposalError::NotSimulatable { reason })?;
+            Ok(SimulateProposalResult::CallResponse { result })
+        }
+        _ => Err(SimulateProposalError::NotSimulatable {
+            reason: "only call and governance update proposals can be simulated".to_owned(),
+        }),
+    }
+}
+
+/// The method mode is taken from the stored did of the canister, the did of the proposal is not trusted.
+async fn simulate_query_call(task: &CallCanister) -> Result<PerformResult, String> {
+    let canister_did = read_state(|state| state.model.canister_did_storage.get_candid(&task.canister_id).cloned())
+        .ok_or("stored canister did is required to check that the method is query")?;
+
+    let (env, actor) = parse_canister_did(&canister_did)?;
+    let function = env
+        .get_method(&actor, &task.method)
+        .map_err(|error| format!("can not find '{}' method in actor: {error:?}", task.method))?;
+
+    if !function
+        .modes
+        .iter()
+        .any(|mode| matches!(mode, FuncMode::Query | FuncMode::CompositeQuery))
+    {
+        return Err(format!("method '{}' is not query", task.method));
+    }
+
+    let method_args = parse_call_argument(task)?;
+    let result = ic_cdk::call::Call::bounded_wait(task.canister_id, task.method.as_str())
+        .with_raw_args(method_args.as_slice())
+        .await
+        .map(|result| result.into_bytes());
+
+    Ok(match result {
+        Ok(raw_response) => decode_call_response(task, raw_response),
+        Err(error) => PerformResult::Error {
+            reason: format!("error while perform canister call: {error:?}"),
+        },
+    })
+}
+
+fn get_governance_diff(current: &Governance, new: &Governance) -> GovernanceDiff {
+    let mut diff = GovernanceDiff::default();
+
+    for (principal, participant) in &new.participants {
+        match current.participants.iter().find(|(p, _)| p == principal) {
+            None => diff.added_participants.push((*principal, participant.clone())),
+            Some((_, old)) if old != participant => diff.changed_participants.push(ParticipantChange {
+                participant: *principal,
+                old: old.clone(),
+                new: participant.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+
+    diff.removed_participants = current
+        .participants
+        .iter()
+        .filter(|(principal, _)| !new.participants.iter().any(|(p, _)| p == principal))
+        .map(|(principal, _)| *principal)
+        .collect();
+
+    let proposal_types = current
+        .voting_configuration
+        .iter()
+        .chain(new.voting_configuration.iter())
+        .map(|(proposal_type, _)| proposal_type.clone())
+        .collect::<BTreeSet<_>>();
+
+    for proposal_type in proposal_types {
+        let find_config = |governance: &Governance| {
+            governance
+                .voting_configuration
+                .iter()
+                .find(|(pt, _)| pt == &proposal_type)
+                .map(|(_, config)| config.clone())
+        };
+
+        let old = find_config(current);
+        let new = find_config(new);
+        if old != new {
+            diff.voting_config_changes
+                .push(VotingConfigChange { proposal_type, old, new });
+        }
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::queries::simulate_proposal::get_governance_diff;
+    use candid::Principal;
+    use governance_canister::types::{Governance, GovernanceParticipant, ProposalPermission, ProposalType, VotingConfig};
+
+    fn participant(name: &str, permissions: Vec<ProposalPermission>) -> GovernanceParticipant {
+        GovernanceParticipant {
+            name: name.to_owned(),
+            proposal_permissions: vec![(ProposalType::UpdateGovernance, permissions)],
+            roles: None,
+            vote_public_key: None,
+        }
+    }
+
+    #[test]
+    fn test_governance_diff() {
+        let alice = Principal::from_slice(&[1]);
+        let bob = Principal::from_slice(&[2]);
+        let carol = Principal::from_slice(&[3]);
+
+        let current = Governance {
+            participants: vec![
+                (alice, participant("alice", vec![ProposalPermission::Add])),
+                (bob, participant("bob", vec![ProposalPermission::Vote])),
+            ],
+            voting_configuration: vec![(
+                ProposalType::UpdateGovernance,
+                VotingConfig {
+                    stop_vote_count: 1,
+                    positive_vote_count: 1,
+                },
+            )],
+            ..Default::default()
+        };
+
+        let new = Governance {
+            participants: vec![
+                (
+                    alice,
+                    participant("alice", vec![ProposalPermission::Add, ProposalPermission::Vote]),
+                ),
+                (carol, participant("carol", vec![ProposalPermission::Vote])),
+            ],
+            voting_configuration: vec![(
+                ProposalType::UpdateGovernance,
+                VotingConfig {
+                    stop_vote_count: 2,
+                    positive_vote_count: 2,
+                },
+            )],
+            ..Default::default()
+        };
+
+        let diff = get_governance_diff(&current, &new);
+        assert_eq!(diff.added_participants.len(), 1);
+        assert_eq!(diff.added_participants[0].0, carol);
+        assert_eq!(diff.removed_participants, vec![bob]);
+        assert_eq!(diff.changed_participants.len(), 1);
+        assert_eq!(diff.changed_participants[0].participant, alice);
+        assert_eq!(diff.voting_config_changes.len(), 1);
+        assert!(diff.voting_config_changes[0].old.is_some());
+    }
+}
@@ -0,0 +1,99 @@
+use governance_canister::types::VotePublicKey;
+
+/// Keys are parsed on governance validation, so a participant can not register a key votes can never be verified with.
+pub(crate) fn validate_public_key(public_key: &VotePublicKey) -> Result<(), String> {
+    match public_key {
+        VotePublicKey::Ed25519 { public_key_der } => {
+            use ed25519_dalek::pkcs8::DecodePublicKey;
+
+            ed25519_dalek::VerifyingKey::from_public_key_der(public_key_der)
+                .map(|_| ())
+                .map_err(|error| format!("can not parse ed25519 public key: {error}"))
+        }
+        VotePublicKey::Secp256k1 { public_key_der } => {
+            use k256::pkcs8::DecodePublicKey;
+
+            k256::ecdsa::VerifyingKey::from_public_key_der(public_key_der)
+                .map(|_| ())
+                .map_err(|error| format!("can not parse secp256k1 public key: {error}"))
+        }
+    }
+}
+
+/// Secp256k1 signatures are verified over the SHA-256 digest of the message, like the agent identities sign it.
+pub(crate) fn verify_signature(public_key: &VotePublicKey, message: &[u8], signature: &[u8]) -> Result<(), String> {
+    match public_key {
+        VotePublicKey::Ed25519 { public_key_der } => {
+            use ed25519_dalek::pkcs8::DecodePublicKey;
+            use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+            let verifying_key = VerifyingKey::from_public_key_der(public_key_der)
+                .map_err(|error| format!("can not parse ed25519 public key: {error}"))?;
+            let signature =
+                Signature::from_slice(signature).map_err(|error| format!("can not parse ed25519 signature: {error}"))?;
+
+            verifying_key
+                .verify(message, &signature)
+                .map_err(|error| format!("ed25519 signature verification failed: {error}"))
+        }
+        VotePublicKey::Secp256k1 { public_key_der } => {
+            use k256::ecdsa::signature::Verifier;
+            use k256::ecdsa::{Signature, VerifyingKey};
+            use k256::pkcs8::DecodePublicKey;
+
+            let verifying_key = VerifyingKey::from_public_key_der(public_key_der)
+                .map_err(|error| format!("can not parse secp256k1 public key: {error}"))?;
+            let signature =
+                Signature::from_slice(signature).map_err(|error| format!("can not parse secp256k1 signature: {error}"))?;
+
+            verifying_key
+                .verify(message, &signature)
+                .map_err(|error| format!("secp256k1 signature verification failed: {error}"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::signature::{validate_public_key, verify_signature};
+    use governance_canister::types::VotePublicKey;
+
+    const MESSAGE: &[u8] = b"vote message";
+
+    #[test]
+    fn test_verify_ed25519_signature() {
+        use ed25519_dalek::pkcs8::EncodePublicKey;
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        let public_key = VotePublicKey::Ed25519 {
+            public_key_der: signing_key.verifying_key().to_public_key_der().unwrap().into_vec(),
+        };
+        let signature = signing_key.sign(MESSAGE).to_bytes();
+
+        assert!(verify_signature(&public_key, MESSAGE, &signature).is_ok());
+        assert!(verify_signature(&public_key, b"other message", &signature).is_err());
+
+        assert!(validate_public_key(&public_key).is_ok());
+        assert!(validate_public_key(&VotePublicKey::Ed25519 {
+            public_key_der: vec![1, 2, 3]
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_verify_secp256k1_signature() {
+        use k256::ecdsa::signature::Signer;
+        use k256::ecdsa::{Signature, SigningKey};
+        use k256::pkcs8::EncodePublicKey;
+
+        let signing_key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let public_key = VotePublicKey::Secp256k1 {
+            public_key_der: signing_key.verifying_key().to_public_key_der().unwrap().into_vec(),
+        };
+        let signature: Signature = signing_key.sign(MESSAGE);
+
+        assert!(verify_signature(&public_key, MESSAGE, &signature.to_bytes()).is_ok());
+        assert!(verify_signature(&public_key, b"other message", &signature.to_bytes()).is_err());
+    }
+}
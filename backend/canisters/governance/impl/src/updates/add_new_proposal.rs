@@ -1,19 +1,27 @@
 use crate::guards::caller_is_governance_user;
+use crate::model::governance::{has_permission, patch_governance};
+use crate::model::DataModel;
+use crate::signature::validate_public_key;
 use crate::time::get_unix_epoch_time_millis;
-use crate::{log_error, log_info, mutate_state};
-use candid::IDLArgs;
+use crate::updates::perform_proposal::call_get_wasm_modules;
+use crate::{log_error, log_info, mutate_state, read_state};
+use candid::types::{Type, TypeInner};
+use candid::{IDLArgs, Principal};
 use candid_parser::parse_idl_args;
+use canister_did::{parse_canister_did, parse_method_response};
 use governance_canister::add_new_proposal::*;
 use governance_canister::types::{
-    CallCanister, Governance, Proposal, ProposalDetail, ProposalPermission, ProposalState, ProposalType, UpgradeCanister,
-    Voting,
+    CallCanister, CanisterScope, DepositCycles, Governance, Proposal, ProposalDetail, ProposalPermission, ProposalState,
+    ProposalType, UpdateApprovedModuleHashes, UpgradeCanister, UpgradeWasmSource, Voting,
 };
-use ic_cdk::api::msg_caller;
+use ic_cdk::api::{canister_self, is_controller, msg_caller};
 use ic_cdk_macros::update;
+use std::collections::HashSet;
+use uploader_canister::get_wasm_modules::GetWasmModulesResponse;
 
 #[update(guard = "caller_is_governance_user")]
-fn add_new_proposal(args: Args) -> Response {
-    match add_new_proposal_int(args) {
+async fn add_new_proposal(args: Args) -> Response {
+    match add_new_proposal_int(args).await {
         Ok(result) => {
             log_info!("Added new proposal: {result:?}");
             Response::Ok(result)
@@ -25,22 +33,37 @@ fn add_new_proposal(args: Args) -> Response {
     }
 }
 
-fn add_new_proposal_int(args: AddNewProposalArgs) -> Result<AddNewProposalResult, AddNewProposalError> {
+async fn add_new_proposal_int(args: AddNewProposalArgs) -> Result<AddNewProposalResult, AddNewProposalError> {
     let caller = msg_caller();
-    let proposal_detail = args.proposal_detail;
+    let mut proposal_detail = args.proposal_detail;
+
+    if let ProposalDetail::UpgradeCanister { task } = &proposal_detail {
+        // the uploader is called only for a caller allowed to add the proposal
+        read_state(|state| check_can_add_proposal(&state.model, &caller, &proposal_detail))?;
+
+        validate_staged_wasm(task)
+            .await
+            .map_err(|reason| AddNewProposalError::Validation { reason })?;
+    }
 
     mutate_state(|state| {
-        let is_add_permission = state.model.governance_storage.check_is_permission(
-            &caller,
-            &ProposalType::from(&proposal_detail),
-            &ProposalPermission::Add,
-        );
+        check_can_add_proposal(&state.model, &caller, &proposal_detail)?;
 
-        if !is_add_permission {
-            return Err(AddNewProposalError::NotPermission);
+        if let ProposalDetail::CallCanister { task } = &mut proposal_detail {
+            if task.canister_did.is_none() {
+                task.canister_did = state.model.canister_did_storage.get_candid(&task.canister_id).cloned();
+            }
         }
 
-        validate_proposal(&proposal_detail).map_err(|reason| AddNewProposalError::Validation { reason })?;
+        validate_proposal(&state.model, &proposal_detail).map_err(|reason| AddNewProposalError::Validation { reason })?;
+
+        let self_targeting = proposal_detail.get_target_canister().map(|target_canister| {
+            state.model.governance_storage.is_self_targeting(
+                &canister_self(),
+                &target_canister,
+                is_controller(&target_canister),
+            )
+        });
 
         let time = get_unix_epoch_time_millis();
         let proposal_id = state.model.proposal_storage.get_new_proposal_id();
@@ -54,6 +77,9 @@ fn add_new_proposal_int(args: AddNewProposalArgs) -> Result<AddNewProposalResult
             detail: proposal_detail,
             description: args.description,
             updated: time,
+            governance_version: Some(state.model.governance_storage.get_version()),
+            reevaluated_version: None,
+            self_targeting,
         };
 
         state.model.proposal_storage.add_new_proposal(proposal_id, proposal.clone());
@@ -62,26 +88,139 @@ fn add_new_proposal_int(args: AddNewProposalArgs) -> Result<AddNewProposalResult
     })
 }
 
-fn validate_proposal(proposal_detail: &ProposalDetail) -> Result<(), String> {
+fn check_can_add_proposal(
+    model: &DataModel,
+    caller: &Principal,
+    proposal_detail: &ProposalDetail,
+) -> Result<(), AddNewProposalError> {
+    if let Some(freeze) = model.freeze_storage.get_active_freeze(get_unix_epoch_time_millis()) {
+        return Err(AddNewProposalError::GovernanceFrozen { until: freeze.until });
+    }
+
+    let is_add_permission = model.governance_storage.check_is_permission(
+        caller,
+        &ProposalType::from(proposal_detail),
+        proposal_detail.get_target_canister().as_ref(),
+        &ProposalPermission::Add,
+    );
+
+    if !is_add_permission {
+        return Err(AddNewProposalError::NotPermission);
+    }
+
+    Ok(())
+}
+
+fn validate_proposal(model: &DataModel, proposal_detail: &ProposalDetail) -> Result<(), String> {
     match proposal_detail {
         ProposalDetail::UpdateGovernance { new_governance } => validate_new_governance(new_governance),
-        ProposalDetail::UpgradeCanister { task } => validate_upgrade_canister(task),
-        ProposalDetail::CallCanister { task } => validate_perform_call(task),
+        ProposalDetail::PatchGovernance { patch } => {
+            validate_new_governance(&patch_governance(model.governance_storage.get_governance(), patch)?)
+        }
+        ProposalDetail::UpgradeCanister { task } => validate_upgrade_canister(model, task),
+        ProposalDetail::CallCanister { task } => validate_perform_call(model, task),
+        ProposalDetail::DepositCycles { task } => validate_deposit_cycles(task),
+        ProposalDetail::UpdateApprovedModuleHashes { task } => validate_update_approved_module_hashes(task),
     }
 }
 
-fn validate_new_governance(governance: &Governance) -> Result<(), String> {
+pub(crate) fn validate_new_governance(governance: &Governance) -> Result<(), String> {
     if governance.participants.is_empty() {
         return Err("participants is empty".to_string());
     }
 
+    if let Some(managed_canisters) = &governance.managed_canisters {
+        let mut canister_ids = HashSet::new();
+        if !managed_canisters
+            .iter()
+            .all(|canister| canister_ids.insert(canister.canister_id))
+        {
+            return Err("managed canisters contain duplicates".to_string());
+        }
+    }
+
+    let roles = governance.roles.as_deref().unwrap_or_default();
+    let mut role_names = HashSet::new();
+    if !roles.iter().all(|role| role_names.insert(role.name.as_str())) {
+        return Err("roles contain duplicates".to_string());
+    }
+
+    let groups = governance.canister_groups.as_deref().unwrap_or_default();
+    let mut group_names = HashSet::new();
+    if !groups.iter().all(|group| group_names.insert(group.name.as_str())) {
+        return Err("canister groups contain duplicates".to_string());
+    }
+
+    let check_scope = |scope: &CanisterScope| match scope {
+        CanisterScope::Group { name } if !group_names.contains(name.as_str()) => {
+            Err(format!("canister scope refers to undefined group '{name}'"))
+        }
+        _ => Ok(()),
+    };
+
+    for role in roles.iter() {
+        role.canister_scope.iter().flatten().try_for_each(check_scope)?;
+    }
+
+    for scoped_config in governance.scoped_voting_configuration.iter().flatten() {
+        check_scope(&scoped_config.scope)?;
+        if scoped_config.voting_config.positive_vote_count > scoped_config.voting_config.stop_vote_count {
+            return Err(format!(
+                "wrong scoped voting config for {:?} proposals",
+                scoped_config.proposal_type
+            ));
+        }
+    }
+
+    if let Some(guardian_config) = &governance.guardians {
+        let mut guardians = HashSet::new();
+        if !guardian_config.guardians.iter().all(|guardian| guardians.insert(guardian)) {
+            return Err("guardians contain duplicates".to_string());
+        }
+
+        if guardian_config.unfreeze_quorum == 0 || guardian_config.unfreeze_quorum as usize > guardians.len() {
+            return Err("wrong guardian unfreeze quorum".to_string());
+        }
+
+        if guardian_config.max_freeze_duration_millis == 0 {
+            return Err("wrong guardian max freeze duration".to_string());
+        }
+    }
+
+    if let Some(call_policy) = &governance.call_policy {
+        let mut canister_ids = HashSet::new();
+        if !call_policy.rules.iter().all(|rule| canister_ids.insert(rule.canister_id)) {
+            return Err("call policy contains duplicate canister rules".to_string());
+        }
+    }
+
+    for (principal, participant) in governance.participants.iter() {
+        if let Some(role_name) = participant
+            .roles
+            .iter()
+            .flatten()
+            .find(|role_name| !role_names.contains(role_name.as_str()))
+        {
+            return Err(format!("participant '{principal}' refers to undefined role '{role_name}'"));
+        }
+
+        if let Some(vote_public_key) = &participant.vote_public_key {
+            validate_public_key(vote_public_key)
+                .map_err(|error| format!("participant '{principal}' has wrong vote public key: {error}"))?;
+        }
+    }
+
     let can_make_new_governance_proposal = governance
         .participants
         .iter()
-        .flat_map(|(_, participant)| {
-            participant.proposal_permissions.iter().find(|(pt, permissions)| {
-                pt == &ProposalType::UpdateGovernance && permissions.contains(&ProposalPermission::Add)
-            })
+        .filter(|(_, participant)| {
+            has_permission(
+                governance,
+                participant,
+                &ProposalType::UpdateGovernance,
+                None,
+                &ProposalPermission::Add,
+            )
         })
         .count();
 
@@ -94,10 +233,14 @@ fn validate_new_governance(governance: &Governance) -> Result<(), String> {
     let can_vote_governance_proposal_count = governance
         .participants
         .iter()
-        .flat_map(|(_, participant)| {
-            participant.proposal_permissions.iter().find(|(pt, permissions)| {
-                pt == &ProposalType::UpdateGovernance && permissions.contains(&ProposalPermission::Vote)
-            })
+        .filter(|(_, participant)| {
+            has_permission(
+                governance,
+                participant,
+                &ProposalType::UpdateGovernance,
+                None,
+                &ProposalPermission::Vote,
+            )
         })
         .count();
 
@@ -112,15 +255,98 @@ fn validate_new_governance(governance: &Governance) -> Result<(), String> {
     if !governance_voting_possible {
         return Err("Wrong voting config for make new governance proposal".to_string());
     }
+
+    let self_targeting_config = governance
+        .self_targeting_voting_config
+        .as_ref()
+        .ok_or("voting config for self-targeting proposals is required")?;
+
+    let governance_voting_stricter = governance.voting_configuration.iter().any(|(proposal_type, config)| {
+        proposal_type == &ProposalType::UpdateGovernance
+            && self_targeting_config.stop_vote_count >= config.stop_vote_count
+            && self_targeting_config.positive_vote_count >= config.positive_vote_count
+    });
+
+    if self_targeting_config.positive_vote_count > self_targeting_config.stop_vote_count || !governance_voting_stricter {
+        return Err(
+            "voting config for self-targeting proposals must be at least as strict as for governance proposals".to_string(),
+        );
+    }
     Ok(())
 }
 
-fn validate_upgrade_canister(upgrade_canister: &UpgradeCanister) -> Result<(), String> {
-    parse_candid(&upgrade_canister.argument_candid).map(|_| ())
+fn validate_upgrade_canister(model: &DataModel, upgrade_canister: &UpgradeCanister) -> Result<(), String> {
+    if !model
+        .approved_module_hash_storage
+        .is_approved(&upgrade_canister.canister_id, &upgrade_canister.module_hash)
+    {
+        return Err(format!(
+            "module hash '{}' is not approved for canister '{}'",
+            upgrade_canister.module_hash, upgrade_canister.canister_id
+        ));
+    }
+
+    parse_upgrade_argument(upgrade_canister)?;
+    if let Some(health_check) = &upgrade_canister.health_check {
+        if health_check.expected_response.is_empty() {
+            return Err("health check expected response is empty".to_string());
+        }
+        parse_candid(&health_check.argument_candid)?;
+        parse_method_response(
+            &health_check.canister_did,
+            &health_check.method,
+            &health_check.expected_response,
+        )
+        .map_err(|error| format!("wrong health check expected response: {error}"))?;
+    }
+    Ok(())
+}
+
+async fn validate_staged_wasm(upgrade_canister: &UpgradeCanister) -> Result<(), String> {
+    if upgrade_canister.wasm_source != Some(UpgradeWasmSource::Staged) {
+        return Ok(());
+    }
+
+    let GetWasmModulesResponse::Ok(result) = call_get_wasm_modules(upgrade_canister.uploader_id)
+        .await
+        .map_err(|error| format!("error while perform uploader canister get_wasm_modules call: {error:?}"))?;
+
+    if !result
+        .wasm_modules
+        .iter()
+        .any(|wasm_module| wasm_module.wasm_hash == upgrade_canister.module_hash)
+    {
+        return Err(format!("wasm '{}' is not staged in uploader", upgrade_canister.module_hash));
+    }
+    Ok(())
 }
 
-fn validate_perform_call(perform_call: &CallCanister) -> Result<(), String> {
-    parse_candid(&perform_call.argument_candid).map(|_| ())
+fn validate_perform_call(model: &DataModel, perform_call: &CallCanister) -> Result<(), String> {
+    model.governance_storage.check_call_policy(perform_call)?;
+    parse_call_argument(perform_call).map(|_| ())
+}
+
+fn validate_deposit_cycles(deposit_cycles: &DepositCycles) -> Result<(), String> {
+    if deposit_cycles.amount == 0 {
+        return Err("deposit amount is zero".to_string());
+    }
+    Ok(())
+}
+
+fn validate_update_approved_module_hashes(update: &UpdateApprovedModuleHashes) -> Result<(), String> {
+    if update.added.is_empty() && update.removed.is_empty() {
+        return Err("module hashes are not changed".to_string());
+    }
+
+    // module hashes are compared with the lowercase hex computed from wasm modules
+    let wrong_hash = update
+        .added
+        .iter()
+        .find(|hash| hash.len() != 64 || !hash.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')));
+    if let Some(hash) = wrong_hash {
+        return Err(format!("wrong module hash '{hash}'"));
+    }
+    Ok(())
 }
 
 pub(crate) fn parse_candid(candid: &str) -> Result<Vec<u8>, String> {
@@ -129,3 +355,77 @@ pub(crate) fn parse_candid(candid: &str) -> Result<Vec<u8>, String> {
 
     args.to_bytes().map_err(|e| format!("can not serialize IDLArgs: {e:?}"))
 }
+
+/// Encodes the upgrade argument, typed by the init args of the new module when its candid interface is known.
+pub(crate) fn parse_upgrade_argument(upgrade_canister: &UpgradeCanister) -> Result<Vec<u8>, String> {
+    match &upgrade_canister.canister_did {
+        Some(canister_did) => parse_candid_with_init_types(&upgrade_canister.argument_candid, canister_did),
+        None => parse_candid(&upgrade_canister.argument_candid),
+    }
+}
+
+/// Encodes the call argument, typed by the method parameters when the candid interface of the canister is known.
+pub(crate) fn parse_call_argument(call_canister: &CallCanister) -> Result<Vec<u8>, String> {
+    match &call_canister.canister_did {
+        Some(canister_did) => {
+            parse_candid_with_method_types(&call_canister.argument_candid, canister_did, &call_canister.method)
+        }
+        None => parse_candid(&call_canister.argument_candid),
+    }
+}
+
+fn parse_candid_with_method_types(candid: &str, canister_did: &str, method: &str) -> Result<Vec<u8>, String> {
+    let (env, actor) = parse_canister_did(canister_did)?;
+    let function = env
+        .get_method(&actor, method)
+        .map_err(|error| format!("can not find '{method}' method in actor: {error:?}"))?;
+    let arg_types: Vec<Type> = function.args.clone();
+
+    let args: IDLArgs = parse_idl_args(candid).map_err(|e| format!("can not parse candid: {e:?}"))?;
+    args.to_bytes_with_types(&env, &arg_types)
+        .map_err(|e| format!("argument does not match '{method}' method args: {e:?}"))
+}
+
+fn parse_candid_with_init_types(candid: &str, canister_did: &str) -> Result<Vec<u8>, String> {
+    let (env, actor) = parse_canister_did(canister_did)?;
+    let init_types: Vec<Type> = match actor.as_ref() {
+        TypeInner::Class(init_args, _) => init_args.clone(),
+        _ => Vec::new(),
+    };
+
+    let args: IDLArgs = parse_idl_args(candid).map_err(|e| format!("can not parse candid: {e:?}"))?;
+    args.to_bytes_with_types(&env, &init_types)
+        .map_err(|e| format!("argument does not match init args of canister did: {e:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::updates::add_new_proposal::{parse_candid_with_init_types, parse_candid_with_method_types};
+
+    const CANISTER_DID: &str = r#"
+        type InitArgs = record { owner : principal; limit : nat32 };
+        service : (InitArgs) -> {
+          get_owner : () -> (principal) query;
+          set_limit : (nat32) -> ();
+        }
+        "#;
+
+    #[test]
+    fn test_upgrade_argument_matches_init_args() {
+        let candid = r#"(record { owner = principal "aaaaa-aa"; limit = 10 })"#;
+        assert!(parse_candid_with_init_types(candid, CANISTER_DID).is_ok());
+    }
+
+    #[test]
+    fn test_upgrade_argument_mistyped() {
+        let candid = r#"(record { owner = "aaaaa-aa"; limit = 10 })"#;
+        assert!(parse_candid_with_init_types(candid, CANISTER_DID).is_err());
+    }
+
+    #[test]
+    fn test_call_argument_typed_by_method() {
+        assert!(parse_candid_with_method_types("(10)", CANISTER_DID, "set_limit").is_ok());
+        assert!(parse_candid_with_method_types("(\"10\")", CANISTER_DID, "set_limit").is_err());
+        assert!(parse_candid_with_method_types("(10)", CANISTER_DID, "unknown").is_err());
+    }
+}
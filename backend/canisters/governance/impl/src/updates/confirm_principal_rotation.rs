@@ -0,0 +1,60 @@
+use crate::guards::caller_is_authorised;
+use crate::principal_rotation::get_rotation_delay;
+use crate::time::get_unix_epoch_time_millis;
+use crate::{log_error, log_info, mutate_state};
+use governance_canister::confirm_principal_rotation::*;
+use ic_cdk::api::msg_caller;
+use ic_cdk_macros::update;
+
+/// The new principal is not a participant yet, so it is not required to be a governance user.
+#[update(guard = "caller_is_authorised")]
+fn confirm_principal_rotation(args: Args) -> Response {
+    let participant = args.participant;
+
+    match confirm_principal_rotation_int(args) {
+        Ok(result) => {
+            log_info!(
+                "Confirmed rotation of participant '{participant}' to '{}', effective at {}",
+                result.rotation.new_principal,
+                result.effective_time
+            );
+            Response::Ok(result)
+        }
+        Err(error) => {
+            log_error!("Can not confirm rotation of participant '{participant}': {error:?}");
+            Response::Err(error)
+        }
+    }
+}
+
+fn confirm_principal_rotation_int(
+    args: ConfirmPrincipalRotationArgs,
+) -> Result<ConfirmPrincipalRotationResult, ConfirmPrincipalRotationError> {
+    let caller = msg_caller();
+
+    mutate_state(|state| {
+        let delay = get_rotation_delay(state.model.governance_storage.get_governance());
+
+        let rotation = state
+            .model
+            .principal_rotation_storage
+            .get_pending_mut(&args.participant)
+            .ok_or(ConfirmPrincipalRotationError::RotationNotFound)?;
+
+        if rotation.new_principal != caller {
+            return Err(ConfirmPrincipalRotationError::NotNewPrincipal);
+        }
+
+        if rotation.confirmed.is_some() {
+            return Err(ConfirmPrincipalRotationError::AlreadyConfirmed);
+        }
+
+        let time = get_unix_epoch_time_millis();
+        rotation.confirmed = Some(time);
+
+        Ok(ConfirmPrincipalRotationResult {
+            rotation: rotation.clone(),
+            effective_time: time.saturating_add(delay),
+        })
+    })
+}
@@ -0,0 +1,52 @@
+use crate::guards::caller_is_governance_user;
+use crate::time::get_unix_epoch_time_millis;
+use crate::{log_error, log_info, mutate_state};
+use governance_canister::delegate_vote::*;
+use ic_cdk::api::msg_caller;
+use ic_cdk_macros::update;
+
+#[update(guard = "caller_is_governance_user")]
+fn delegate_vote(args: Args) -> Response {
+    let caller = msg_caller();
+
+    match delegate_vote_int(args) {
+        Ok(_) => {
+            log_info!("Vote delegation of '{caller}' is updated");
+            Response::Ok
+        }
+        Err(error) => {
+            log_error!("Can not update vote delegation of '{caller}': {error:?}");
+            Response::Err(error)
+        }
+    }
+}
+
+/// The delegate must hold the `Vote` permission for a proposal at vote time to cast the delegated vote.
+fn delegate_vote_int(args: DelegateVoteArgs) -> Result<(), DelegateVoteError> {
+    let caller = msg_caller();
+
+    mutate_state(|state| {
+        if let Some(delegation) = &args.delegation {
+            if delegation.delegate == caller {
+                return Err(DelegateVoteError::SelfDelegation);
+            }
+
+            if state
+                .model
+                .governance_storage
+                .get_governance_participant(&delegation.delegate)
+                .is_none()
+            {
+                return Err(DelegateVoteError::DelegateNotParticipant);
+            }
+
+            if delegation.expiration <= get_unix_epoch_time_millis() {
+                return Err(DelegateVoteError::WrongExpiration);
+            }
+        }
+
+        state.model.vote_delegation_storage.set_delegation(caller, args.delegation);
+
+        Ok(())
+    })
+}
@@ -0,0 +1,74 @@
+use crate::guards::caller_is_authorised;
+use crate::time::get_unix_epoch_time_millis;
+use crate::{log_error, log_info, mutate_state};
+use governance_canister::freeze_governance::*;
+use governance_canister::types::GovernanceFreeze;
+use ic_cdk::api::msg_caller;
+use ic_cdk_macros::update;
+
+/// Guardians are not required to be governance participants.
+#[update(guard = "caller_is_authorised")]
+fn freeze_governance(args: Args) -> Response {
+    let caller = msg_caller();
+
+    match freeze_governance_int(args) {
+        Ok(result) => {
+            log_info!(
+                "Governance is frozen by guardian '{caller}' until {}, reason: {:?}",
+                result.freeze.until,
+                result.freeze.reason
+            );
+            Response::Ok(result)
+        }
+        Err(error) => {
+            log_error!("Can not freeze governance by '{caller}': {error:?}");
+            Response::Err(error)
+        }
+    }
+}
+
+fn freeze_governance_int(args: FreezeGovernanceArgs) -> Result<FreezeGovernanceResult, FreezeGovernanceError> {
+    let caller = msg_caller();
+
+    mutate_state(|state| {
+        let guardian_config = state
+            .model
+            .governance_storage
+            .get_governance()
+            .guardians
+            .as_ref()
+            .filter(|config| config.guardians.contains(&caller))
+            .ok_or(FreezeGovernanceError::NotGuardian)?;
+
+        if args.duration_millis == 0 || args.duration_millis > guardian_config.max_freeze_duration_millis {
+            return Err(FreezeGovernanceError::WrongDuration {
+                max_duration_millis: guardian_config.max_freeze_duration_millis,
+            });
+        }
+
+        let time = get_unix_epoch_time_millis();
+        if state.model.freeze_storage.get_active_freeze(time).is_some() {
+            return Err(FreezeGovernanceError::AlreadyFrozen);
+        }
+
+        if let Some(until) = state
+            .model
+            .freeze_storage
+            .get_cooldown_end(time, guardian_config.max_freeze_duration_millis)
+        {
+            return Err(FreezeGovernanceError::CoolingDown { until });
+        }
+
+        let freeze = GovernanceFreeze {
+            guardian: caller,
+            reason: args.reason,
+            started: time,
+            until: time.saturating_add(args.duration_millis),
+            unfreeze_votes: vec![],
+        };
+
+        state.model.freeze_storage.set_freeze(freeze.clone());
+
+        Ok(FreezeGovernanceResult { freeze })
+    })
+}
@@ -1,18 +1,23 @@
+use crate::canister_status::{check_cycles_reserve, deposit_cycles};
 use crate::guards::caller_is_governance_user;
 use crate::time::get_unix_epoch_time_millis;
-use crate::updates::add_new_proposal::parse_candid;
+use crate::updates::add_new_proposal::{parse_call_argument, parse_candid, parse_upgrade_argument, validate_new_governance};
 use crate::{log_error, log_info, mutate_state, read_state};
-use candid::{IDLArgs, Principal, TypeEnv};
-use candid_parser::{check_prog, IDLProg};
+use candid::{IDLArgs, Principal};
+use canister_did::decode_method_response;
 use governance_canister::perform_proposal::*;
 use governance_canister::types::{
-    CallCanister, PerformResult, ProposalDetail, ProposalPermission, ProposalState, ProposalType, UpgradeCanister,
+    CallCanister, GovernanceVersion, PerformResult, ProposalDetail, ProposalPermission, ProposalState, ProposalType,
+    UpgradeCanister, UpgradeWasmSource,
 };
-use ic_cdk::api::msg_caller;
+use ic_cdk::api::{canister_self, msg_caller};
 use ic_cdk::call::CallResult;
 use ic_cdk_macros::update;
+use uploader_canister::announce_wasm_hash::{AnnounceWasmHashArgs, AnnounceWasmHashResponse};
+use uploader_canister::get_wasm_modules::GetWasmModulesResponse;
+use uploader_canister::perform_operation::PerformOperationResponse;
 use uploader_canister::set_operation_grant::{SetOperationGrantArgs, SetOperationGrantResponse};
-use uploader_canister::types::{OperationGrant, OperationType, WasmProperties};
+use uploader_canister::types::{EmptyArgs, HealthCheck, OperationGrant, OperationType, WasmProperties, WasmSource};
 
 #[update(guard = "caller_is_governance_user")]
 async fn perform_proposal(args: Args) -> Response {
@@ -34,11 +39,15 @@ async fn perform_proposal_int(args: PerformProposalArgs) -> Result<PerformPropos
     let caller = msg_caller();
     let proposal_id = args.proposal_id;
 
-    let proposal_detail = read_state(|state| {
+    let (proposal_detail, governance_version) = mutate_state(|state| {
+        if let Some(freeze) = state.model.freeze_storage.get_active_freeze(get_unix_epoch_time_millis()) {
+            return Err(PerformProposalError::GovernanceFrozen { until: freeze.until });
+        }
+
         let proposal = state
             .model
             .proposal_storage
-            .get_proposal(&proposal_id)
+            .get_proposal_mut(&proposal_id)
             .ok_or(PerformProposalError::ProposalNotFound)?;
 
         if !matches!(proposal.state, ProposalState::Approved) {
@@ -47,18 +56,23 @@ async fn perform_proposal_int(args: PerformProposalArgs) -> Result<PerformPropos
 
         let proposal_type = ProposalType::from(&proposal.detail);
 
-        if !state
-            .model
-            .governance_storage
-            .check_is_permission(&caller, &proposal_type, &ProposalPermission::Perform)
-        {
+        if !state.model.governance_storage.check_is_permission(
+            &caller,
+            &proposal_type,
+            proposal.detail.get_target_canister().as_ref(),
+            &ProposalPermission::Perform,
+        ) {
             return Err(PerformProposalError::NotPermission);
         }
 
-        Ok(proposal.detail.clone())
+        // locks the proposal, so a concurrent call can not perform it again while the task is awaited
+        proposal.state = ProposalState::Performing;
+        proposal.updated = get_unix_epoch_time_millis();
+
+        Ok((proposal.detail.clone(), proposal.governance_version.unwrap_or_default()))
     })?;
 
-    let result = perform_proposal_task(&proposal_detail).await;
+    let result = perform_proposal_task(&proposal_detail, governance_version).await;
 
     let proposal = mutate_state(|state| {
         let proposal = state
@@ -67,25 +81,41 @@ async fn perform_proposal_int(args: PerformProposalArgs) -> Result<PerformPropos
             .get_proposal_mut(&proposal_id)
             .ok_or(PerformProposalError::ProposalNotFound)?;
 
-        if !matches!(proposal.state, ProposalState::Approved) {
+        if !matches!(proposal.state, ProposalState::Performing) {
             return Err(PerformProposalError::ProposalIsNotApprovedState);
         }
 
+        let time = get_unix_epoch_time_millis();
         proposal.state = ProposalState::Performed { result };
-        proposal.updated = get_unix_epoch_time_millis();
+        proposal.updated = time;
+        let proposal = proposal.clone();
+
+        state
+            .model
+            .proposal_storage
+            .apply_stale_proposal_policy(&state.model.governance_storage, time);
 
-        Ok(proposal.clone())
+        Ok(proposal)
     })?;
 
     Ok(PerformProposalResult { proposal })
 }
 
-async fn perform_proposal_task(proposal_detail: &ProposalDetail) -> PerformResult {
+async fn perform_proposal_task(proposal_detail: &ProposalDetail, governance_version: GovernanceVersion) -> PerformResult {
     match proposal_detail {
         ProposalDetail::UpdateGovernance { new_governance } => {
             mutate_state(|state| state.model.governance_storage.set_new_governance(new_governance.clone()));
             PerformResult::Done
         }
+        ProposalDetail::PatchGovernance { patch } => match mutate_state(|state| {
+            state
+                .model
+                .governance_storage
+                .apply_patch(patch, governance_version, validate_new_governance)
+        }) {
+            Ok(_) => PerformResult::Done,
+            Err(reason) => PerformResult::Error { reason },
+        },
         ProposalDetail::UpgradeCanister { task } => match perform_upgrade_canister(task).await {
             Ok(_) => PerformResult::Done,
             Err(reason) => PerformResult::Error { reason },
@@ -94,20 +124,77 @@ async fn perform_proposal_task(proposal_detail: &ProposalDetail) -> PerformResul
             Ok(raw_response) => decode_call_response(task, raw_response),
             Err(reason) => PerformResult::Error { reason },
         },
+        ProposalDetail::UpdateApprovedModuleHashes { task } => {
+            mutate_state(|state| {
+                state
+                    .model
+                    .approved_module_hash_storage
+                    .update_hashes(task.canister_id, &task.added, &task.removed)
+            });
+            PerformResult::Done
+        }
+        ProposalDetail::DepositCycles { task } => {
+            if let Err(reason) = check_cycles_reserve(task.amount) {
+                return PerformResult::Error { reason };
+            }
+
+            match deposit_cycles(task.canister_id, task.amount).await {
+                Ok(_) => PerformResult::Done,
+                Err(reason) => PerformResult::Error { reason },
+            }
+        }
     }
 }
 
 async fn perform_upgrade_canister(task: &UpgradeCanister) -> Result<(), String> {
+    if !read_state(|state| {
+        state
+            .model
+            .approved_module_hash_storage
+            .is_approved(&task.canister_id, &task.module_hash)
+    }) {
+        return Err(format!("module hash '{}' is no longer approved", task.module_hash));
+    }
+
+    call_announce_wasm_hash(
+        task.uploader_id,
+        AnnounceWasmHashArgs {
+            canister_id: task.canister_id,
+            wasm_hash: task.module_hash.clone(),
+        },
+    )
+    .await
+    .map_err(|error| format!("error while perform uploader canister announce_wasm_hash call: {error:?}"))?;
+
+    let staged = task.wasm_source == Some(UpgradeWasmSource::Staged);
+
     let args = SetOperationGrantArgs {
         grant: Some(OperationGrant {
-            operator: task.operator_id,
+            operator: if staged { canister_self() } else { task.operator_id },
             canister_id: task.canister_id,
             operation_type: OperationType::UpgradeCode,
             wasm_properties: WasmProperties {
-                wasm_length: None,
+                wasm_length: task
+                    .source
+                    .as_ref()
+                    .and_then(|source| source.wasm_length)
+                    .map(|wasm_length| wasm_length as usize),
                 wasm_hash: task.module_hash.clone(),
             },
-            arg: parse_candid(task.argument_candid.as_str())?,
+            arg: parse_upgrade_argument(task)?,
+            store_canister_id: None,
+            wasm_source: staged.then_some(WasmSource::Registry),
+            take_snapshot: task.take_snapshot,
+            health_check: match &task.health_check {
+                Some(health_check) => Some(HealthCheck {
+                    method: health_check.method.clone(),
+                    arg: parse_candid(health_check.argument_candid.as_str())?,
+                    canister_did: health_check.canister_did.clone(),
+                    expected_response: health_check.expected_response.clone(),
+                    rollback_on_failure: health_check.rollback_on_failure,
+                }),
+                None => None,
+            },
         }),
     };
 
@@ -115,14 +202,59 @@ async fn perform_upgrade_canister(task: &UpgradeCanister) -> Result<(), String>
         .await
         .map_err(|error| format!("error while perform uploader canister call: {error:?}"))?;
 
-    match result {
-        SetOperationGrantResponse::Ok => Ok(()),
-        SetOperationGrantResponse::Err(error) => Err(format!(
+    if let SetOperationGrantResponse::Err(error) = result {
+        return Err(format!(
             "error while perform uploader canister set_operation_grant call: {error:?}"
+        ));
+    }
+
+    if !staged {
+        return Ok(());
+    }
+
+    match call_perform_operation(task.uploader_id)
+        .await
+        .map_err(|error| format!("error while perform uploader canister perform_operation call: {error:?}"))?
+    {
+        PerformOperationResponse::Ok => {
+            log_info!("Staged wasm '{}' installed", task.module_hash);
+            Ok(())
+        }
+        PerformOperationResponse::OkWithSnapshot { snapshot_id } => {
+            log_info!("Staged wasm '{}' installed, snapshot: {snapshot_id:?}", task.module_hash);
+            Ok(())
+        }
+        PerformOperationResponse::Err(error) => Err(format!(
+            "error while perform uploader canister perform_operation call: {error:?}"
         )),
     }
 }
 
+/// Install may take several rounds, so the call waits for the response without a deadline.
+async fn call_perform_operation(uploader_canister: Principal) -> CallResult<PerformOperationResponse> {
+    Ok(ic_cdk::call::Call::unbounded_wait(uploader_canister, "perform_operation")
+        .with_arg(EmptyArgs {})
+        .await?
+        .candid()?)
+}
+
+pub(crate) async fn call_get_wasm_modules(uploader_canister: Principal) -> CallResult<GetWasmModulesResponse> {
+    Ok(ic_cdk::call::Call::bounded_wait(uploader_canister, "get_wasm_modules")
+        .with_arg(EmptyArgs {})
+        .await?
+        .candid()?)
+}
+
+async fn call_announce_wasm_hash(
+    uploader_canister: Principal,
+    args: AnnounceWasmHashArgs,
+) -> CallResult<AnnounceWasmHashResponse> {
+    Ok(ic_cdk::call::Call::bounded_wait(uploader_canister, "announce_wasm_hash")
+        .with_arg(args)
+        .await?
+        .candid()?)
+}
+
 async fn call_set_operation_grant(
     uploader_canister: Principal,
     args: SetOperationGrantArgs,
@@ -134,10 +266,12 @@ async fn call_set_operation_grant(
 }
 
 async fn perform_canister_call(task: &CallCanister) -> Result<Vec<u8>, String> {
+    read_state(|state| state.model.governance_storage.check_call_policy(task))?;
+
     let canister_id = task.canister_id;
     let method = task.method.as_str();
     let payment = task.payment.unwrap_or(0) as u128;
-    let method_args = parse_candid(task.argument_candid.as_str())?;
+    let method_args = parse_call_argument(task)?;
 
     ic_cdk::call::Call::bounded_wait(canister_id, method)
         .with_raw_args(method_args.as_slice())
@@ -147,7 +281,7 @@ async fn perform_canister_call(task: &CallCanister) -> Result<Vec<u8>, String> {
         .map_err(|error| format!("error while perform canister call: {error:?}"))
 }
 
-fn decode_call_response(task: &CallCanister, raw: Vec<u8>) -> PerformResult {
+pub(crate) fn decode_call_response(task: &CallCanister, raw: Vec<u8>) -> PerformResult {
     if task.canister_did.is_some() {
         match decode_call_response_with_did(task, raw.as_slice()) {
             Ok(idl_args) => PerformResult::CallResponse {
@@ -233,26 +367,6 @@ fn decode_call_response_with_did(task: &CallCanister, raw: &[u8]) -> Result<IDLA
     // decode_method_response(task.canister_did.as_ref().unwrap().as_str(), task.method.as_str(), raw)
 }
 
-pub(crate) fn decode_method_response(canister_did: &str, method: &str, raw: &[u8]) -> Result<IDLArgs, String> {
-    let ast: IDLProg = canister_did
-        .parse()
-        .map_err(|error| format!("can not parse canister did {error:?}"))?;
-
-    let mut env = TypeEnv::new();
-    let actor = check_prog(&mut env, &ast)
-        .map_err(|error| format!("can not parse canister did {error:?}"))?
-        .ok_or("can not find actor in canister did")?;
-
-    let method = env
-        .get_method(&actor, method)
-        .map_err(|error| format!("can not find '{method}' method in actor: {error:?}"))?;
-
-    let return_arg_types = method.rets.as_slice();
-
-    IDLArgs::from_bytes_with_types(raw, &env, return_arg_types)
-        .map_err(|error| format!("can not parse raw with types: {error:?}"))
-}
-
 // #[cfg(test)]
 // mod tests {
 //     use candid::{Decode, Encode, IDLValue, Principal};
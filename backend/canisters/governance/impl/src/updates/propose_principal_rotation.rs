@@ -0,0 +1,67 @@
+use crate::guards::caller_is_governance_user;
+use crate::time::get_unix_epoch_time_millis;
+use crate::{log_error, log_info, mutate_state};
+use candid::Principal;
+use governance_canister::propose_principal_rotation::*;
+use governance_canister::types::PrincipalRotation;
+use ic_cdk::api::msg_caller;
+use ic_cdk_macros::update;
+
+#[update(guard = "caller_is_governance_user")]
+fn propose_principal_rotation(args: Args) -> Response {
+    let caller = msg_caller();
+    let new_principal = args.new_principal;
+
+    match propose_principal_rotation_int(args) {
+        Ok(result) => {
+            log_info!("Proposed rotation of participant '{caller}' to '{new_principal}'");
+            Response::Ok(result)
+        }
+        Err(error) => {
+            log_error!("Can not propose rotation of participant '{caller}' to '{new_principal}': {error:?}");
+            Response::Err(error)
+        }
+    }
+}
+
+fn propose_principal_rotation_int(
+    args: ProposePrincipalRotationArgs,
+) -> Result<ProposePrincipalRotationResult, ProposePrincipalRotationError> {
+    let caller = msg_caller();
+    let new_principal = args.new_principal;
+
+    if new_principal == Principal::anonymous() {
+        return Err(ProposePrincipalRotationError::AnonymousPrincipal);
+    }
+
+    mutate_state(|state| {
+        if state
+            .model
+            .governance_storage
+            .get_governance_participant(&new_principal)
+            .is_some()
+        {
+            return Err(ProposePrincipalRotationError::NewPrincipalIsParticipant);
+        }
+
+        if state
+            .model
+            .principal_rotation_storage
+            .is_pending_new_principal(&new_principal)
+        {
+            return Err(ProposePrincipalRotationError::NewPrincipalIsPending);
+        }
+
+        let rotation = PrincipalRotation {
+            participant: caller,
+            new_principal,
+            proposed: get_unix_epoch_time_millis(),
+            confirmed: None,
+            completed: None,
+        };
+
+        state.model.principal_rotation_storage.set_pending(rotation.clone());
+
+        Ok(ProposePrincipalRotationResult { rotation })
+    })
+}
@@ -0,0 +1,97 @@
+use crate::guards::caller_is_governance_user;
+use crate::model::canister_did::CanisterDid;
+use crate::{log_error, log_info, mutate_state, read_state};
+use candid::Principal;
+use governance_canister::refresh_canister_did::*;
+use ic_cdk::call::CallResult;
+use ic_cdk_macros::update;
+use uploader_canister::get_wasm_module_candid::{
+    GetWasmModuleCandidArgs, GetWasmModuleCandidError, GetWasmModuleCandidResponse,
+};
+
+#[update(guard = "caller_is_governance_user")]
+async fn refresh_canister_did(args: Args) -> Response {
+    let canister_id = args.canister_id;
+
+    match refresh_canister_did_int(canister_id).await {
+        Ok(result) => {
+            log_info!(
+                "Candid of canister '{canister_id}' refreshed for module '{}'",
+                result.module_hash
+            );
+            Response::Ok(result)
+        }
+        Err(error) => {
+            log_error!("Can not refresh candid of canister '{canister_id}': {error:?}");
+            Response::Err(error)
+        }
+    }
+}
+
+/// Takes the candid interface stored by the uploader registry for the module the canister currently runs.
+pub(crate) async fn refresh_canister_did_int(
+    canister_id: Principal,
+) -> Result<RefreshCanisterDidResult, RefreshCanisterDidError> {
+    let (uploader_id, module_hash) = read_state(|state| {
+        let canister = state
+            .model
+            .governance_storage
+            .get_managed_canisters()
+            .iter()
+            .find(|canister| canister.canister_id == canister_id)
+            .ok_or(RefreshCanisterDidError::CanisterNotManaged)?;
+
+        let module_hash = state
+            .model
+            .canister_status_storage
+            .get_entry(&canister_id)
+            .and_then(|entry| entry.status.as_ref())
+            .and_then(|status| status.module_hash.clone())
+            .ok_or(RefreshCanisterDidError::ModuleHashUnknown)?;
+
+        Ok((canister.uploader_id, module_hash))
+    })?;
+
+    let response = call_get_wasm_module_candid(
+        uploader_id,
+        GetWasmModuleCandidArgs {
+            wasm_hash: module_hash.clone(),
+        },
+    )
+    .await
+    .map_err(|error| RefreshCanisterDidError::CallError {
+        reason: format!("error while perform uploader canister call: {error:?}"),
+    })?;
+
+    let canister_did = match response {
+        GetWasmModuleCandidResponse::Ok(result) => result.candid_service.ok_or(RefreshCanisterDidError::CandidNotFound)?,
+        GetWasmModuleCandidResponse::Err(GetWasmModuleCandidError::WasmNotFound) => {
+            return Err(RefreshCanisterDidError::CandidNotFound)
+        }
+    };
+
+    mutate_state(|state| {
+        state.model.canister_did_storage.set_did(
+            canister_id,
+            CanisterDid {
+                module_hash: module_hash.clone(),
+                candid: canister_did.clone(),
+            },
+        )
+    });
+
+    Ok(RefreshCanisterDidResult {
+        module_hash,
+        canister_did,
+    })
+}
+
+async fn call_get_wasm_module_candid(
+    uploader_canister: Principal,
+    args: GetWasmModuleCandidArgs,
+) -> CallResult<GetWasmModuleCandidResponse> {
+    Ok(ic_cdk::call::Call::bounded_wait(uploader_canister, "get_wasm_module_candid")
+        .with_arg(args)
+        .await?
+        .candid()?)
+}
@@ -0,0 +1,66 @@
+use crate::guards::caller_is_authorised;
+use crate::time::get_unix_epoch_time_millis;
+use crate::{log_error, log_info, mutate_state};
+use governance_canister::unfreeze_governance::*;
+use ic_cdk::api::msg_caller;
+use ic_cdk_macros::update;
+
+#[update(guard = "caller_is_authorised")]
+fn unfreeze_governance(args: Args) -> Response {
+    let caller = msg_caller();
+
+    match unfreeze_governance_int(args) {
+        Ok(result) => {
+            match &result {
+                UnfreezeGovernanceResult::Unfrozen => {
+                    log_info!("Governance is unfrozen, last vote by guardian '{caller}'")
+                }
+                UnfreezeGovernanceResult::Voted { freeze } => log_info!(
+                    "Guardian '{caller}' voted to unfreeze governance: {} votes",
+                    freeze.unfreeze_votes.len()
+                ),
+            }
+            Response::Ok(result)
+        }
+        Err(error) => {
+            log_error!("Can not unfreeze governance by '{caller}': {error:?}");
+            Response::Err(error)
+        }
+    }
+}
+
+fn unfreeze_governance_int(_args: Args) -> Result<UnfreezeGovernanceResult, UnfreezeGovernanceError> {
+    let caller = msg_caller();
+
+    mutate_state(|state| {
+        let time = get_unix_epoch_time_millis();
+        let unfreeze_quorum = state
+            .model
+            .governance_storage
+            .get_governance()
+            .guardians
+            .as_ref()
+            .filter(|config| config.guardians.contains(&caller))
+            .map(|config| config.unfreeze_quorum)
+            .ok_or(UnfreezeGovernanceError::NotGuardian)?;
+
+        let freeze = state
+            .model
+            .freeze_storage
+            .get_active_freeze_mut(time)
+            .ok_or(UnfreezeGovernanceError::NotFrozen)?;
+
+        if freeze.unfreeze_votes.contains(&caller) {
+            return Err(UnfreezeGovernanceError::AlreadyVoted);
+        }
+
+        freeze.unfreeze_votes.push(caller);
+
+        if (freeze.unfreeze_votes.len() as u32) < unfreeze_quorum {
+            return Ok(UnfreezeGovernanceResult::Voted { freeze: freeze.clone() });
+        }
+
+        state.model.freeze_storage.end_freeze(time);
+        Ok(UnfreezeGovernanceResult::Unfrozen)
+    })
+}
@@ -0,0 +1,39 @@
+use crate::guards::caller_is_governance_user;
+use crate::{log_error, log_info, mutate_state};
+use governance_canister::veto_principal_rotation::*;
+use ic_cdk::api::msg_caller;
+use ic_cdk_macros::update;
+
+/// Any participant can veto a pending rotation, the participant itself cancels it this way.
+#[update(guard = "caller_is_governance_user")]
+fn veto_principal_rotation(args: Args) -> Response {
+    let caller = msg_caller();
+    let participant = args.participant;
+
+    match veto_principal_rotation_int(args) {
+        Ok(result) => {
+            log_info!(
+                "Rotation of participant '{participant}' to '{}' is vetoed by '{caller}'",
+                result.rotation.new_principal
+            );
+            Response::Ok(result)
+        }
+        Err(error) => {
+            log_error!("Can not veto rotation of participant '{participant}': {error:?}");
+            Response::Err(error)
+        }
+    }
+}
+
+fn veto_principal_rotation_int(
+    args: VetoPrincipalRotationArgs,
+) -> Result<VetoPrincipalRotationResult, VetoPrincipalRotationError> {
+    mutate_state(|state| {
+        state
+            .model
+            .principal_rotation_storage
+            .remove_pending(&args.participant)
+            .map(|rotation| VetoPrincipalRotationResult { rotation })
+            .ok_or(VetoPrincipalRotationError::RotationNotFound)
+    })
+}
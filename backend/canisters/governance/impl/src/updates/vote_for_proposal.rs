@@ -1,12 +1,15 @@
-use crate::guards::caller_is_governance_user;
+use crate::guards::caller_is_authorised;
+use crate::model::proposal::check_voting_finish;
+use crate::signature::verify_signature;
 use crate::time::get_unix_epoch_time_millis;
 use crate::{log_error, log_info, mutate_state};
-use governance_canister::types::{Proposal, ProposalPermission, ProposalState, ProposalType, Vote, VotingConfig};
+use governance_canister::types::{ProposalPermission, ProposalState, ProposalType, Vote};
 use governance_canister::vote_for_proposal::*;
-use ic_cdk::api::msg_caller;
+use ic_cdk::api::{canister_self, msg_caller};
 use ic_cdk_macros::update;
 
-#[update(guard = "caller_is_governance_user")]
+/// Signed votes can be submitted by any caller, other votes are checked against the caller permissions.
+#[update(guard = "caller_is_authorised")]
 fn vote_for_proposal(args: Args) -> Response {
     let proposal_id = args.proposal_id;
 
@@ -23,11 +26,34 @@ fn vote_for_proposal(args: Args) -> Response {
 }
 
 fn vote_for_proposal_int(args: VoteForProposalArgs) -> Result<VoteForProposalResult, VoteForProposalError> {
-    let caller = msg_caller();
     let proposal_id = args.proposal_id;
     let vote = args.vote;
 
     mutate_state(|state| {
+        let voter = match &args.signed_vote {
+            None => msg_caller(),
+            Some(signed_vote) => {
+                let vote_public_key = state
+                    .model
+                    .governance_storage
+                    .get_governance_participant(&signed_vote.participant)
+                    .and_then(|participant| participant.vote_public_key.as_ref())
+                    .ok_or(VoteForProposalError::VotePublicKeyNotRegistered)?;
+
+                let message = get_vote_signing_message(
+                    &canister_self(),
+                    proposal_id,
+                    vote,
+                    state.model.governance_storage.get_version(),
+                );
+
+                verify_signature(vote_public_key, &message, &signed_vote.signature)
+                    .map_err(|reason| VoteForProposalError::WrongSignature { reason })?;
+
+                signed_vote.participant
+            }
+        };
+
         let proposal = state
             .model
             .proposal_storage
@@ -38,33 +64,61 @@ fn vote_for_proposal_int(args: VoteForProposalArgs) -> Result<VoteForProposalRes
             return Err(VoteForProposalError::ProposalIsNotVotingState);
         }
 
-        if proposal.voting.votes.iter().any(|vote| vote.participant == caller) {
+        if proposal.voting.votes.iter().any(|vote| vote.get_voter() == voter) {
             return Err(VoteForProposalError::AlreadyVoted);
         }
 
         let proposal_type = ProposalType::from(&proposal.detail);
+        let target_canister = proposal.detail.get_target_canister();
         let voting_config = state
             .model
             .governance_storage
-            .get_voting_configuration(&proposal_type)
+            .get_proposal_voting_configuration(proposal)
             .ok_or(VoteForProposalError::VotingConfigNotFound)?;
 
-        if !state
-            .model
-            .governance_storage
-            .check_is_permission(&caller, &proposal_type, &ProposalPermission::Vote)
-        {
+        if !state.model.governance_storage.check_is_permission(
+            &voter,
+            &proposal_type,
+            target_canister.as_ref(),
+            &ProposalPermission::Vote,
+        ) {
             return Err(VoteForProposalError::NotPermission);
         }
 
         let time = get_unix_epoch_time_millis();
 
         proposal.voting.votes.push(Vote {
-            participant: caller,
+            participant: voter,
             vote_time: time,
             vote,
+            on_behalf_of: None,
         });
 
+        for delegator in state
+            .model
+            .vote_delegation_storage
+            .get_delegators(&voter, &proposal_type, time)
+        {
+            let already_voted = proposal.voting.votes.iter().any(|vote| vote.get_voter() == delegator);
+            if already_voted
+                || !state.model.governance_storage.check_is_permission(
+                    &delegator,
+                    &proposal_type,
+                    target_canister.as_ref(),
+                    &ProposalPermission::Vote,
+                )
+            {
+                continue;
+            }
+
+            proposal.voting.votes.push(Vote {
+                participant: voter,
+                vote_time: time,
+                vote,
+                on_behalf_of: Some(delegator),
+            });
+        }
+
         proposal.updated = time;
 
         check_voting_finish(proposal, voting_config);
@@ -74,23 +128,3 @@ fn vote_for_proposal_int(args: VoteForProposalArgs) -> Result<VoteForProposalRes
         })
     })
 }
-
-fn check_voting_finish(proposal: &mut Proposal, voting_config: &VotingConfig) {
-    if (proposal.voting.votes.len() as u32) < voting_config.stop_vote_count {
-        return;
-    }
-
-    let mut positive = 0;
-
-    for vote in proposal.voting.votes.iter() {
-        if vote.vote {
-            positive += 1;
-        }
-    }
-
-    proposal.state = if positive >= voting_config.positive_vote_count {
-        ProposalState::Approved
-    } else {
-        ProposalState::Declined
-    };
-}
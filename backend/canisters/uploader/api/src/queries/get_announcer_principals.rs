@@ -0,0 +1,16 @@
+use crate::types::EmptyArgs;
+use candid::{CandidType, Principal};
+use serde::Deserialize;
+
+pub type Args = EmptyArgs;
+pub type Response = GetAnnouncerPrincipalsResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum GetAnnouncerPrincipalsResponse {
+    Ok(GetAnnouncerPrincipalsResult),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct GetAnnouncerPrincipalsResult {
+    pub announcer_principals: Vec<Principal>,
+}
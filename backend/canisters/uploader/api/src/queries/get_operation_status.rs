@@ -0,0 +1,23 @@
+use crate::types::{EmptyArgs, OperationGrant, WasmLength};
+use candid::CandidType;
+use serde::Deserialize;
+
+pub type Args = EmptyArgs;
+pub type Response = GetOperationStatusResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum GetOperationStatusResponse {
+    Ok(GetOperationStatusResult),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct GetOperationStatusResult {
+    pub grant: Option<OperationGrant>,
+    pub uploaded_wasm: Option<UploadedWasm>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct UploadedWasm {
+    pub wasm_length: WasmLength,
+    pub wasm_hash: String,
+}
@@ -0,0 +1,27 @@
+use crate::types::{Rollout, RolloutId};
+use candid::CandidType;
+use serde::Deserialize;
+
+pub type Args = GetRolloutArgs;
+pub type Response = GetRolloutResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct GetRolloutArgs {
+    pub rollout_id: RolloutId,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum GetRolloutResponse {
+    Ok(GetRolloutResult),
+    Err(GetRolloutError),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct GetRolloutResult {
+    pub rollout: Rollout,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum GetRolloutError {
+    RolloutNotFound,
+}
@@ -0,0 +1,27 @@
+use crate::types::WasmHash;
+use candid::CandidType;
+use serde::Deserialize;
+
+pub type Args = GetWasmModuleCandidArgs;
+pub type Response = GetWasmModuleCandidResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct GetWasmModuleCandidArgs {
+    pub wasm_hash: WasmHash,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum GetWasmModuleCandidResponse {
+    Ok(GetWasmModuleCandidResult),
+    Err(GetWasmModuleCandidError),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct GetWasmModuleCandidResult {
+    pub candid_service: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum GetWasmModuleCandidError {
+    WasmNotFound,
+}
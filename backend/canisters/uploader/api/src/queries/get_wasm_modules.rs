@@ -0,0 +1,16 @@
+use crate::types::{EmptyArgs, WasmModuleInfo};
+use candid::CandidType;
+use serde::Deserialize;
+
+pub type Args = EmptyArgs;
+pub type Response = GetWasmModulesResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum GetWasmModulesResponse {
+    Ok(GetWasmModulesResult),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct GetWasmModulesResult {
+    pub wasm_modules: Vec<WasmModuleInfo>,
+}
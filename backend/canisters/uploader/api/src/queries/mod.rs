@@ -1,3 +1,8 @@
+pub mod get_announcer_principals;
 pub mod get_canistergeek_information;
 pub mod get_geek_user_principals;
+pub mod get_operation_status;
+pub mod get_rollout;
 pub mod get_service_principals;
+pub mod get_wasm_module_candid;
+pub mod get_wasm_modules;
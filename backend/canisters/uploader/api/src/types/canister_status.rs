@@ -0,0 +1,53 @@
+use candid::{CandidType, Nat, Principal};
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CanisterStatus {
+    pub status: CanisterRunningStatus,
+    pub module_hash: Option<String>,
+    pub controllers: Vec<Principal>,
+    pub memory_size: Nat,
+    pub memory_metrics: CanisterMemoryMetrics,
+    pub cycles: Nat,
+    pub reserved_cycles: Nat,
+    pub idle_cycles_burned_per_day: Nat,
+    pub query_stats: CanisterQueryStats,
+    pub settings: CanisterStatusSettings,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum CanisterRunningStatus {
+    Running,
+    Stopping,
+    Stopped,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CanisterMemoryMetrics {
+    pub wasm_memory_size: Nat,
+    pub stable_memory_size: Nat,
+    pub global_memory_size: Nat,
+    pub wasm_binary_size: Nat,
+    pub custom_sections_size: Nat,
+    pub canister_history_size: Nat,
+    pub wasm_chunk_store_size: Nat,
+    pub snapshots_size: Nat,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CanisterQueryStats {
+    pub num_calls_total: Nat,
+    pub num_instructions_total: Nat,
+    pub request_payload_bytes_total: Nat,
+    pub response_payload_bytes_total: Nat,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CanisterStatusSettings {
+    pub compute_allocation: Nat,
+    pub memory_allocation: Nat,
+    pub freezing_threshold: Nat,
+    pub reserved_cycles_limit: Nat,
+    pub wasm_memory_limit: Nat,
+    pub wasm_memory_threshold: Nat,
+}
@@ -10,6 +10,10 @@ pub struct OperationGrant {
     pub operation_type: OperationType,
     pub wasm_properties: WasmProperties,
     pub arg: Vec<u8>,
+    pub store_canister_id: Option<Principal>,
+    pub wasm_source: Option<WasmSource>,
+    pub take_snapshot: Option<bool>,
+    pub health_check: Option<HealthCheck>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -19,6 +23,23 @@ pub enum OperationType {
     UpgradeCode,
 }
 
+/// Method called on the canister after install, its decoded candid response must be equal to `expected_response`,
+/// the candid text of the response values, e.g. `(variant { Ok })`.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct HealthCheck {
+    pub method: String,
+    pub arg: Vec<u8>,
+    pub canister_did: String,
+    pub expected_response: String,
+    pub rollback_on_failure: Option<bool>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum WasmSource {
+    Upload,
+    Registry,
+}
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct WasmProperties {
     pub wasm_length: Option<WasmLength>,
@@ -1,5 +1,11 @@
 mod api;
+mod canister_status;
 mod grant;
+mod rollout;
+mod wasm;
 
 pub use api::*;
+pub use canister_status::*;
 pub use grant::*;
+pub use rollout::*;
+pub use wasm::*;
@@ -0,0 +1,51 @@
+use crate::types::{OperationType, WasmHash};
+use candid::{CandidType, Principal};
+use serde::{Deserialize, Serialize};
+
+pub type RolloutId = u64;
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Rollout {
+    pub rollout_id: RolloutId,
+    pub initiator: Principal,
+    pub wasm_hash: WasmHash,
+    pub operation_type: OperationType,
+    pub shared_arg: Vec<u8>,
+    pub policy: RolloutPolicy,
+    pub state: RolloutState,
+    pub targets: Vec<RolloutTargetStatus>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct RolloutPolicy {
+    pub concurrency: u32,
+    pub canary_count: u32,
+    pub halt_on_failure: bool,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct RolloutTarget {
+    pub canister_id: Principal,
+    pub arg: Option<Vec<u8>>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct RolloutTargetStatus {
+    pub target: RolloutTarget,
+    pub state: RolloutTargetState,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum RolloutState {
+    Running,
+    Halted { reason: String },
+    Completed,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum RolloutTargetState {
+    Pending,
+    Installing,
+    Installed,
+    Failed { reason: String },
+}
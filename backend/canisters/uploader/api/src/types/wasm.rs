@@ -0,0 +1,20 @@
+use crate::types::WasmLength;
+use candid::{CandidType, Principal};
+use serde::{Deserialize, Serialize};
+
+pub type WasmHash = String;
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct WasmModuleMetadata {
+    pub name: String,
+    pub version: Option<String>,
+    pub git_commit: Option<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct WasmModuleInfo {
+    pub wasm_hash: WasmHash,
+    pub wasm_length: WasmLength,
+    pub metadata: WasmModuleMetadata,
+    pub uploaded_by: Principal,
+}
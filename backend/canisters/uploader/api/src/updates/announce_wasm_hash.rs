@@ -0,0 +1,17 @@
+use crate::types::WasmHash;
+use candid::{CandidType, Principal};
+use serde::Deserialize;
+
+pub type Args = AnnounceWasmHashArgs;
+pub type Response = AnnounceWasmHashResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct AnnounceWasmHashArgs {
+    pub canister_id: Principal,
+    pub wasm_hash: WasmHash,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum AnnounceWasmHashResponse {
+    Ok,
+}
@@ -1,3 +1,4 @@
+use crate::types::CanisterStatus;
 use candid::{CandidType, Principal};
 use serde::Deserialize;
 
@@ -9,6 +10,7 @@ pub struct GetCanisterStatusArgs {
     pub canister_id: Principal,
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(CandidType, Deserialize, Debug)]
 pub enum GetCanisterStatusResponse {
     Ok(GetCanisterStatusResult),
@@ -17,7 +19,7 @@ pub enum GetCanisterStatusResponse {
 
 #[derive(CandidType, Deserialize, Debug)]
 pub struct GetCanisterStatusResult {
-    pub status: String,
+    pub status: CanisterStatus,
 }
 
 #[derive(CandidType, Deserialize, Debug)]
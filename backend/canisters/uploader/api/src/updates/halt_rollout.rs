@@ -0,0 +1,23 @@
+use crate::types::RolloutId;
+use candid::CandidType;
+use serde::Deserialize;
+
+pub type Args = HaltRolloutArgs;
+pub type Response = HaltRolloutResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct HaltRolloutArgs {
+    pub rollout_id: RolloutId,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum HaltRolloutResponse {
+    Ok,
+    Err(HaltRolloutError),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum HaltRolloutError {
+    RolloutNotFound,
+    RolloutIsNotRunning,
+}
@@ -8,6 +8,7 @@ pub type Response = PerformOperationResponse;
 #[derive(CandidType, Deserialize, Debug)]
 pub enum PerformOperationResponse {
     Ok,
+    OkWithSnapshot { snapshot_id: Vec<u8> },
     Err(PerformOperationError),
 }
 
@@ -16,4 +17,6 @@ pub enum PerformOperationError {
     WrongWasmLength { length: WasmLength },
     WrongWasmHash { hash: String },
     OperationError { reason: String },
+    RolledBack { reason: String, snapshot_id: Vec<u8> },
+    HealthCheckFailed { response: String, rolled_back: bool },
 }
@@ -0,0 +1,31 @@
+use crate::types::{WasmHash, WasmModuleInfo, WasmModuleMetadata};
+use candid::CandidType;
+use serde::Deserialize;
+
+pub type Args = RegisterWasmModuleArgs;
+pub type Response = RegisterWasmModuleResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct RegisterWasmModuleArgs {
+    pub wasm_hash: WasmHash,
+    pub metadata: WasmModuleMetadata,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum RegisterWasmModuleResponse {
+    Ok(RegisterWasmModuleResult),
+    Err(RegisterWasmModuleError),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct RegisterWasmModuleResult {
+    pub wasm_module: WasmModuleInfo,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum RegisterWasmModuleError {
+    NothingUploaded,
+    WrongWasmHash { hash: WasmHash },
+    AlreadyRegistered,
+    TooManyModules,
+}
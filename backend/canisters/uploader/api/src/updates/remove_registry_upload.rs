@@ -0,0 +1,22 @@
+use candid::{CandidType, Principal};
+use serde::Deserialize;
+
+pub type Args = RemoveRegistryUploadArgs;
+pub type Response = RemoveRegistryUploadResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct RemoveRegistryUploadArgs {
+    /// The caller's upload is removed if not set.
+    pub uploader: Option<Principal>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum RemoveRegistryUploadResponse {
+    Ok,
+    Err(RemoveRegistryUploadError),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum RemoveRegistryUploadError {
+    UploadNotFound,
+}
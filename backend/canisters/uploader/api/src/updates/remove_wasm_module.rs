@@ -0,0 +1,23 @@
+use crate::types::{RolloutId, WasmHash};
+use candid::CandidType;
+use serde::Deserialize;
+
+pub type Args = RemoveWasmModuleArgs;
+pub type Response = RemoveWasmModuleResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct RemoveWasmModuleArgs {
+    pub wasm_hash: WasmHash,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum RemoveWasmModuleResponse {
+    Ok,
+    Err(RemoveWasmModuleError),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum RemoveWasmModuleError {
+    WasmNotFound,
+    UsedByRunningRollout { rollout_id: RolloutId },
+}
@@ -0,0 +1,15 @@
+use candid::{CandidType, Principal};
+use serde::Deserialize;
+
+pub type Args = SetAnnouncerPrincipalsArgs;
+pub type Response = SetAnnouncerPrincipalsResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct SetAnnouncerPrincipalsArgs {
+    pub announcer_principals: Vec<Principal>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum SetAnnouncerPrincipalsResponse {
+    Ok,
+}
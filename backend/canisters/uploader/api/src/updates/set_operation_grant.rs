@@ -19,4 +19,7 @@ pub enum SetOperationGrantResponse {
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub enum SetOperationGrantError {
     WrongWasmLength,
+    WasmNotFoundInRegistry,
+    WasmHashNotAnnounced,
+    WrongHealthCheck { reason: String },
 }
@@ -0,0 +1,34 @@
+use crate::types::{OperationType, RolloutId, RolloutPolicy, RolloutTarget, WasmHash};
+use candid::{CandidType, Principal};
+use serde::Deserialize;
+
+pub type Args = StartRolloutArgs;
+pub type Response = StartRolloutResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct StartRolloutArgs {
+    pub wasm_hash: WasmHash,
+    pub operation_type: OperationType,
+    pub targets: Vec<RolloutTarget>,
+    pub shared_arg: Vec<u8>,
+    pub policy: RolloutPolicy,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum StartRolloutResponse {
+    Ok(StartRolloutResult),
+    Err(StartRolloutError),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct StartRolloutResult {
+    pub rollout_id: RolloutId,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum StartRolloutError {
+    WasmNotFoundInRegistry,
+    WasmHashNotAnnounced { canister_id: Principal },
+    WrongTargets { reason: String },
+    WrongPolicy { reason: String },
+}
@@ -0,0 +1,29 @@
+use crate::types::WasmLength;
+use candid::CandidType;
+use serde::Deserialize;
+
+pub type Args = UploadRegistryWasmChunkArgs;
+pub type Response = UploadRegistryWasmChunkResponse;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct UploadRegistryWasmChunkArgs {
+    pub first: bool,
+    pub chunk: Vec<u8>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum UploadRegistryWasmChunkResponse {
+    Ok(UploadRegistryWasmChunkResult),
+    Err(UploadRegistryWasmChunkError),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct UploadRegistryWasmChunkResult {
+    pub length: WasmLength,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum UploadRegistryWasmChunkError {
+    WasmLengthOverflow,
+    RegistrySizeOverflow,
+}
@@ -5,11 +5,25 @@ use uploader_canister::*;
 
 // Queries
 generate_query_call!(get_service_principals);
+generate_query_call!(get_announcer_principals);
 generate_query_call!(get_geek_user_principals);
+generate_query_call!(get_wasm_modules);
+generate_query_call!(get_wasm_module_candid);
+generate_query_call!(get_rollout);
+generate_query_call!(get_operation_status);
 
 // Updates
+generate_update_call!(announce_wasm_hash);
+generate_update_call!(get_canister_status);
 generate_update_call!(set_service_principals);
+generate_update_call!(set_announcer_principals);
 generate_update_call!(set_geek_user_principals);
 generate_update_call!(set_operation_grant);
 generate_update_call!(upload_wasm_chunk);
 generate_update_call!(perform_operation);
+generate_update_call!(upload_registry_wasm_chunk);
+generate_update_call!(register_wasm_module);
+generate_update_call!(remove_wasm_module);
+generate_update_call!(remove_registry_upload);
+generate_update_call!(start_rollout);
+generate_update_call!(halt_rollout);
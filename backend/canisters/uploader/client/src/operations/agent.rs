@@ -32,6 +32,14 @@ pub fn get_dfx_identity_from_pem_content(pem_content: Vec<u8>) -> Result<Box<dyn
     }
 }
 
+/// Returns the DER encoded public key and the signature, e.g. to sign a governance vote relayed by another caller.
+pub fn sign_arbitrary_content(identity: &dyn Identity, content: &[u8]) -> Result<(Vec<u8>, Vec<u8>), String> {
+    let signature = identity.sign_arbitrary(content)?;
+    let public_key = signature.public_key.ok_or("identity has no public key")?;
+    let signature = signature.signature.ok_or("identity returned no signature")?;
+    Ok((public_key, signature))
+}
+
 pub async fn build_ic_agent(main_net: bool, url: String, identity: Box<dyn Identity>) -> Agent {
     let timeout = std::time::Duration::from_secs(60 * 5);
 
@@ -2,9 +2,15 @@ use candid::Principal;
 use ic_agent::Agent;
 use sha2::{Digest, Sha256};
 use std::cmp::min;
+use uploader_canister::announce_wasm_hash::{AnnounceWasmHashArgs, AnnounceWasmHashResponse};
+use uploader_canister::get_canister_status::{GetCanisterStatusArgs, GetCanisterStatusResponse};
 use uploader_canister::perform_operation::PerformOperationResponse;
+use uploader_canister::register_wasm_module::{RegisterWasmModuleArgs, RegisterWasmModuleResponse};
 use uploader_canister::set_operation_grant::{SetOperationGrantArgs, SetOperationGrantResponse};
-use uploader_canister::types::{EmptyArgs, OperationGrant, WasmProperties};
+use uploader_canister::types::{
+    CanisterStatus, EmptyArgs, OperationGrant, WasmHash, WasmModuleInfo, WasmModuleMetadata, WasmProperties, WasmSource,
+};
+use uploader_canister::upload_registry_wasm_chunk::{UploadRegistryWasmChunkArgs, UploadRegistryWasmChunkResponse};
 use uploader_canister::upload_wasm_chunk::{UploadWasmChunkArgs, UploadWasmChunkResponse};
 
 pub type OperationType = uploader_canister::types::OperationType;
@@ -28,6 +34,10 @@ pub async fn set_operation_grant(
             wasm_hash,
         },
         arg,
+        store_canister_id: None,
+        wasm_source: None,
+        take_snapshot: None,
+        health_check: None,
     });
 
     match crate::set_operation_grant(agent, uploader_canister_id, &SetOperationGrantArgs { grant }).await {
@@ -36,6 +46,47 @@ pub async fn set_operation_grant(
     }
 }
 
+pub async fn announce_wasm_hash(
+    agent: &Agent,
+    uploader_canister_id: &Principal,
+    canister_id: Principal,
+    wasm_hash: WasmHash,
+) -> Result<(), String> {
+    match crate::announce_wasm_hash(agent, uploader_canister_id, &AnnounceWasmHashArgs { canister_id, wasm_hash }).await {
+        Ok(AnnounceWasmHashResponse::Ok) => Ok(()),
+        response => Err(format!("Error while announce wasm hash: {:?}", response)),
+    }
+}
+
+pub async fn set_registry_operation_grant(
+    agent: &Agent,
+    uploader_canister_id: &Principal,
+    canister_id: Principal,
+    operation_type: OperationType,
+    wasm_hash: String,
+    arg: Vec<u8>,
+) -> Result<(), String> {
+    let grant = Some(OperationGrant {
+        operator: agent.get_principal().unwrap(),
+        canister_id,
+        operation_type,
+        wasm_properties: WasmProperties {
+            wasm_length: None,
+            wasm_hash,
+        },
+        arg,
+        store_canister_id: None,
+        wasm_source: Some(WasmSource::Registry),
+        take_snapshot: None,
+        health_check: None,
+    });
+
+    match crate::set_operation_grant(agent, uploader_canister_id, &SetOperationGrantArgs { grant }).await {
+        Ok(SetOperationGrantResponse::Ok) => Ok(()),
+        response => Err(format!("Error while set registry operation grant: {:?}", response)),
+    }
+}
+
 pub async fn put_wasm_to_uploader(agent: &Agent, uploader_canister_id: &Principal, wasm: Vec<u8>) -> Result<(), String> {
     let mut from: usize = 0;
     let mut first = true;
@@ -68,13 +119,73 @@ pub async fn put_wasm_to_uploader(agent: &Agent, uploader_canister_id: &Principa
     Ok(())
 }
 
-pub async fn perform_operation(agent: &Agent, uploader_canister_id: &Principal) -> Result<(), String> {
+pub async fn put_wasm_to_registry(
+    agent: &Agent,
+    uploader_canister_id: &Principal,
+    wasm: Vec<u8>,
+    metadata: WasmModuleMetadata,
+) -> Result<WasmModuleInfo, String> {
+    let mut from: usize = 0;
+    let mut first = true;
+    while from < wasm.len() {
+        let to = min(from + 2_000_000, wasm.len());
+
+        println!("Put registry wasm chunk [{from}..{to}] ...");
+
+        match crate::upload_registry_wasm_chunk(
+            agent,
+            uploader_canister_id,
+            &UploadRegistryWasmChunkArgs {
+                first,
+                chunk: wasm[from..to].to_owned(),
+            },
+        )
+        .await
+        {
+            Ok(UploadRegistryWasmChunkResponse::Ok(_)) => {}
+            response => {
+                return Err(format!("Error while put registry wasm chunk: {:?}", response));
+            }
+        }
+
+        first = false;
+        from = to;
+    }
+
+    let args = RegisterWasmModuleArgs {
+        wasm_hash: get_module_hash(&wasm),
+        metadata,
+    };
+
+    match crate::register_wasm_module(agent, uploader_canister_id, &args).await {
+        Ok(RegisterWasmModuleResponse::Ok(result)) => {
+            println!("Wasm registered!");
+            Ok(result.wasm_module)
+        }
+        response => Err(format!("Error while register wasm module: {:?}", response)),
+    }
+}
+
+/// Returns the id of the snapshot kept for the canister, if it was requested by the grant.
+pub async fn perform_operation(agent: &Agent, uploader_canister_id: &Principal) -> Result<Option<Vec<u8>>, String> {
     match crate::perform_operation(agent, uploader_canister_id, &EmptyArgs {}).await {
-        Ok(PerformOperationResponse::Ok) => Ok(()),
+        Ok(PerformOperationResponse::Ok) => Ok(None),
+        Ok(PerformOperationResponse::OkWithSnapshot { snapshot_id }) => Ok(Some(snapshot_id)),
         response => Err(format!("Error while perform operation: {:?}", response)),
     }
 }
 
+pub async fn get_canister_status(
+    agent: &Agent,
+    uploader_canister_id: &Principal,
+    canister_id: Principal,
+) -> Result<CanisterStatus, String> {
+    match crate::get_canister_status(agent, uploader_canister_id, &GetCanisterStatusArgs { canister_id }).await {
+        Ok(GetCanisterStatusResponse::Ok(result)) => Ok(result.status),
+        response => Err(format!("Error while get canister status: {:?}", response)),
+    }
+}
+
 pub fn get_module_hash(module: &Vec<u8>) -> String {
     let mut hasher = Sha256::new();
     hasher.update(module);
@@ -21,3 +21,19 @@ pub fn caller_is_operator() -> Result<(), String> {
         Err("Caller is not operator".to_owned())
     }
 }
+
+pub fn caller_is_announcer() -> Result<(), String> {
+    if crate::read_state(|state| state.caller_is_announcer()) {
+        Ok(())
+    } else {
+        Err("Caller is not announcer".to_owned())
+    }
+}
+
+pub fn caller_is_controller() -> Result<(), String> {
+    if ic_cdk::api::is_controller(&ic_cdk::api::msg_caller()) {
+        Ok(())
+    } else {
+        Err("Caller is not controller".to_owned())
+    }
+}
@@ -0,0 +1,83 @@
+use candid::Principal;
+use canister_did::{decode_method_response, parse_method_response};
+use uploader_canister::types::HealthCheck;
+
+/// Calls the health check method and returns the decoded response, or the reason of failure as error.
+pub(crate) async fn perform_health_check(canister_id: Principal, health_check: &HealthCheck) -> Result<String, String> {
+    let raw = ic_cdk::call::Call::bounded_wait(canister_id, health_check.method.as_str())
+        .with_raw_args(health_check.arg.as_slice())
+        .await
+        .map(|result| result.into_bytes())
+        .map_err(|error| format!("error while perform health check call: {error:?}"))?;
+
+    check_health_check_response(health_check, raw.as_slice())
+}
+
+fn check_health_check_response(health_check: &HealthCheck, raw: &[u8]) -> Result<String, String> {
+    let response = decode_method_response(health_check.canister_did.as_str(), health_check.method.as_str(), raw)?;
+
+    let expected_response = parse_method_response(
+        health_check.canister_did.as_str(),
+        health_check.method.as_str(),
+        health_check.expected_response.as_str(),
+    )?;
+
+    if response == expected_response {
+        Ok(response.to_string())
+    } else {
+        Err(response.to_string())
+    }
+}
+
+pub(crate) fn validate_health_check(health_check: &HealthCheck) -> Result<(), String> {
+    if health_check.expected_response.is_empty() {
+        return Err("expected response is empty".to_owned());
+    }
+
+    parse_method_response(
+        health_check.canister_did.as_str(),
+        health_check.method.as_str(),
+        health_check.expected_response.as_str(),
+    )
+    .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::health_check::{check_health_check_response, validate_health_check};
+    use candid_parser::parse_idl_args;
+    use uploader_canister::types::HealthCheck;
+
+    const CANISTER_DID: &str = "service : { health : () -> (variant { Ok; Err : text }) query }";
+
+    fn health_check(expected_response: &str) -> HealthCheck {
+        HealthCheck {
+            method: "health".to_owned(),
+            arg: Vec::new(),
+            canister_did: CANISTER_DID.to_owned(),
+            expected_response: expected_response.to_owned(),
+            rollback_on_failure: None,
+        }
+    }
+
+    fn encode(response: &str) -> Vec<u8> {
+        parse_idl_args(response).unwrap().to_bytes().unwrap()
+    }
+
+    #[test]
+    fn test_check_health_check_response() {
+        let health_check = health_check("(variant { Ok })");
+        assert!(validate_health_check(&health_check).is_ok());
+
+        assert!(check_health_check_response(&health_check, &encode("(variant { Ok })")).is_ok());
+        // the error text contains the expected response, but the value differs
+        assert!(check_health_check_response(&health_check, &encode("(variant { Err = \"Ok\" })")).is_err());
+    }
+
+    #[test]
+    fn test_validate_health_check_expected_response() {
+        assert!(validate_health_check(&health_check("")).is_err());
+        assert!(validate_health_check(&health_check("Ok")).is_err());
+        assert!(validate_health_check(&health_check("(true)")).is_err());
+    }
+}
@@ -1,11 +1,15 @@
 mod guards;
+mod health_check;
 mod lifecycle;
 mod macros;
 mod management;
 mod model;
 mod queries;
+mod rollout;
 mod serializer;
 mod state;
+mod time;
 mod updates;
+mod wasm_metadata;
 
 canister_state!(state::CanisterState);
@@ -24,5 +24,7 @@ fn post_upgrade() {
         }
     };
 
+    crate::rollout::resume_rollouts();
+
     log_info!("Post-upgrade completed!");
 }
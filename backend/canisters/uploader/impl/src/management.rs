@@ -1,26 +1,33 @@
+use crate::log_error;
 use sha2::Digest;
 use sha2::Sha256;
-use std::cmp::min;
-use uploader_canister::types::OperationType;
+use std::collections::HashSet;
+use uploader_canister::types::{
+    CanisterMemoryMetrics, CanisterQueryStats, CanisterRunningStatus, CanisterStatus, CanisterStatusSettings, OperationType,
+};
 
 use candid::Principal;
 use ic_cdk::management_canister::{
-    canister_status, clear_chunk_store, install_chunked_code, update_settings, upload_chunk, CanisterInstallMode,
-    CanisterSettings, CanisterStatusArgs, ChunkHash, ClearChunkStoreArgs, InstallChunkedCodeArgs, UpdateSettingsArgs,
-    UploadChunkArgs,
+    canister_status, clear_chunk_store, delete_canister_snapshot, install_chunked_code, list_canister_snapshots,
+    load_canister_snapshot, stored_chunks, take_canister_snapshot, update_settings, upload_chunk, CanisterInstallMode,
+    CanisterSettings, CanisterStatusArgs, CanisterStatusResult, CanisterStatusType, ChunkHash, ClearChunkStoreArgs,
+    DeleteCanisterSnapshotArgs, InstallChunkedCodeArgs, ListCanisterSnapshotsArgs, LoadCanisterSnapshotArgs, SnapshotId,
+    StoredChunksArgs, TakeCanisterSnapshotArgs, UpdateSettingsArgs, UploadChunkArgs,
 };
 
+const CHUNK_SIZE: usize = 1_000_000;
+
 pub(crate) async fn install_canister_code(
     operation_type: OperationType,
     canister_id: Principal,
+    store_canister_id: Option<Principal>,
     wasm_module: Vec<u8>,
     arg: Vec<u8>,
 ) -> Result<(), String> {
-    let mut hasher = Sha256::new();
-    hasher.update(&wasm_module);
-    let wasm_module_hash = hasher.finalize().to_vec();
+    let wasm_module_hash = get_sha256(&wasm_module);
+    let store_canister = store_canister_id.unwrap_or(canister_id);
 
-    let chunk_hashes_list = put_wasm_module(&canister_id, wasm_module).await?;
+    let chunk_hashes_list = put_wasm_module(&store_canister, &wasm_module).await?;
 
     let arg = InstallChunkedCodeArgs {
         mode: match operation_type {
@@ -29,7 +36,7 @@ pub(crate) async fn install_canister_code(
             OperationType::UpgradeCode => CanisterInstallMode::Upgrade(None),
         },
         target_canister: canister_id,
-        store_canister: None,
+        store_canister: store_canister_id,
         chunk_hashes_list,
         arg,
         wasm_module_hash,
@@ -37,38 +44,104 @@ pub(crate) async fn install_canister_code(
 
     install_chunked_code(&arg)
         .await
-        .map_err(|error| format!("Error while installing canister code: {:?}", error))
+        .map_err(|error| format!("Error while installing canister code: {:?}", error))?;
+
+    // chunks are kept on failure so a retry only uploads the missing ones;
+    // a dedicated store canister is shared by other installs, so its chunks are never cleared
+    if store_canister == canister_id {
+        if let Err(error) = clear_chunk_store(&ClearChunkStoreArgs {
+            canister_id: store_canister,
+        })
+        .await
+        {
+            log_error!("Can not clear chunk store of '{store_canister}': {error:?}");
+        }
+    }
+
+    Ok(())
 }
 
-pub async fn put_wasm_module(canister_id: &Principal, wasm_module: Vec<u8>) -> Result<Vec<ChunkHash>, String> {
-    clear_chunk_store(&ClearChunkStoreArgs {
-        canister_id: *canister_id,
+pub async fn put_wasm_module(store_canister_id: &Principal, wasm_module: &[u8]) -> Result<Vec<ChunkHash>, String> {
+    let mut stored_hashes: HashSet<Vec<u8>> = stored_chunks(&StoredChunksArgs {
+        canister_id: *store_canister_id,
     })
     .await
-    .map_err(|error| format!("Error while clearing chunk store: {:?}", error))?;
+    .map_err(|error| format!("Error while getting stored chunks: {:?}", error))?
+    .into_iter()
+    .map(|chunk_hash| chunk_hash.hash)
+    .collect();
 
-    let chunk_size = 1_000_000;
-    let mut from: usize = 0;
     let mut hashes = vec![];
-    while from < wasm_module.len() {
-        let to = min(from + chunk_size, wasm_module.len());
+    for chunk in wasm_module.chunks(CHUNK_SIZE) {
+        let hash = get_sha256(chunk);
 
-        let arg = UploadChunkArgs {
-            canister_id: *canister_id,
-            chunk: wasm_module[from..to].to_owned(),
-        };
+        if !stored_hashes.contains(&hash) {
+            let arg = UploadChunkArgs {
+                canister_id: *store_canister_id,
+                chunk: chunk.to_owned(),
+            };
 
-        let chunk_hash = upload_chunk(&arg)
-            .await
-            .map_err(|error| format!("Error while uploading chunk: {:?}", error))?;
+            upload_chunk(&arg)
+                .await
+                .map_err(|error| format!("Error while uploading chunk: {:?}", error))?;
 
-        hashes.push(chunk_hash);
-        from = to;
+            stored_hashes.insert(hash.clone());
+        }
+
+        hashes.push(ChunkHash { hash });
     }
 
     Ok(hashes)
 }
 
+fn get_sha256(bytes: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    hasher.finalize().to_vec()
+}
+
+/// Replaces the snapshot previously taken by the uploader, so snapshots do not pile up to the canister limit.
+pub(crate) async fn take_snapshot(
+    canister_id: Principal,
+    previous_snapshot_id: Option<SnapshotId>,
+) -> Result<SnapshotId, String> {
+    let replace_snapshot = match previous_snapshot_id {
+        Some(previous_snapshot_id) => list_canister_snapshots(&ListCanisterSnapshotsArgs { canister_id })
+            .await
+            .map_err(|error| format!("Error while listing canister snapshots: {:?}", error))?
+            .into_iter()
+            .map(|snapshot| snapshot.id)
+            .find(|snapshot_id| snapshot_id == &previous_snapshot_id),
+        None => None,
+    };
+
+    take_canister_snapshot(&TakeCanisterSnapshotArgs {
+        canister_id,
+        replace_snapshot,
+    })
+    .await
+    .map(|snapshot| snapshot.id)
+    .map_err(|error| format!("Error while taking canister snapshot: {:?}", error))
+}
+
+pub(crate) async fn delete_snapshot(canister_id: Principal, snapshot_id: SnapshotId) -> Result<(), String> {
+    delete_canister_snapshot(&DeleteCanisterSnapshotArgs {
+        canister_id,
+        snapshot_id,
+    })
+    .await
+    .map_err(|error| format!("Error while deleting canister snapshot: {:?}", error))
+}
+
+pub(crate) async fn load_snapshot(canister_id: Principal, snapshot_id: SnapshotId) -> Result<(), String> {
+    load_canister_snapshot(&LoadCanisterSnapshotArgs {
+        canister_id,
+        snapshot_id,
+    })
+    .await
+    .map_err(|error| format!("Error while loading canister snapshot: {:?}", error))
+}
+
 pub(crate) async fn set_controllers(canister_id: Principal, controllers: Vec<Principal>) -> Result<(), String> {
     update_settings(&UpdateSettingsArgs {
         canister_id,
@@ -88,9 +161,49 @@ pub(crate) async fn set_controllers(canister_id: Principal, controllers: Vec<Pri
     .map_err(|e| format!("{:?}", e))
 }
 
-pub(crate) async fn get_canister_status(canister_id: Principal) -> Result<String, String> {
+pub(crate) async fn get_canister_status(canister_id: Principal) -> Result<CanisterStatus, String> {
     canister_status(&CanisterStatusArgs { canister_id })
         .await
         .map_err(|e| format!("{:?}", e))
-        .map(|status| format!("{:?}", status))
+        .map(to_canister_status)
+}
+
+fn to_canister_status(result: CanisterStatusResult) -> CanisterStatus {
+    CanisterStatus {
+        status: match result.status {
+            CanisterStatusType::Running => CanisterRunningStatus::Running,
+            CanisterStatusType::Stopping => CanisterRunningStatus::Stopping,
+            CanisterStatusType::Stopped => CanisterRunningStatus::Stopped,
+        },
+        module_hash: result.module_hash.map(hex::encode),
+        controllers: result.settings.controllers,
+        memory_size: result.memory_size,
+        memory_metrics: CanisterMemoryMetrics {
+            wasm_memory_size: result.memory_metrics.wasm_memory_size,
+            stable_memory_size: result.memory_metrics.stable_memory_size,
+            global_memory_size: result.memory_metrics.global_memory_size,
+            wasm_binary_size: result.memory_metrics.wasm_binary_size,
+            custom_sections_size: result.memory_metrics.custom_sections_size,
+            canister_history_size: result.memory_metrics.canister_history_size,
+            wasm_chunk_store_size: result.memory_metrics.wasm_chunk_store_size,
+            snapshots_size: result.memory_metrics.snapshots_size,
+        },
+        cycles: result.cycles,
+        reserved_cycles: result.reserved_cycles,
+        idle_cycles_burned_per_day: result.idle_cycles_burned_per_day,
+        query_stats: CanisterQueryStats {
+            num_calls_total: result.query_stats.num_calls_total,
+            num_instructions_total: result.query_stats.num_instructions_total,
+            request_payload_bytes_total: result.query_stats.request_payload_bytes_total,
+            response_payload_bytes_total: result.query_stats.response_payload_bytes_total,
+        },
+        settings: CanisterStatusSettings {
+            compute_allocation: result.settings.compute_allocation,
+            memory_allocation: result.settings.memory_allocation,
+            freezing_threshold: result.settings.freezing_threshold,
+            reserved_cycles_limit: result.settings.reserved_cycles_limit,
+            wasm_memory_limit: result.settings.wasm_memory_limit,
+            wasm_memory_threshold: result.settings.wasm_memory_threshold,
+        },
+    }
 }
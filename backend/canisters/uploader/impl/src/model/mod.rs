@@ -1,13 +1,28 @@
+use crate::model::rollout::RolloutStorage;
+use crate::model::wasm_registry::WasmRegistry;
 use candid::Principal;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
-use uploader_canister::types::OperationGrant;
+use std::collections::{HashMap, HashSet};
+use uploader_canister::types::{OperationGrant, WasmHash};
+
+pub mod rollout;
+pub mod wasm_registry;
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct DataModel {
     service_principals: HashSet<Principal>,
     geek_user_principals: HashSet<Principal>,
     current_operation: Option<CurrentOperation>,
+    #[serde(default)]
+    wasm_registry: WasmRegistry,
+    #[serde(default)]
+    rollout_storage: RolloutStorage,
+    #[serde(default)]
+    announced_wasm_hashes: HashMap<Principal, HashSet<WasmHash>>,
+    #[serde(default)]
+    announcer_principals: HashSet<Principal>,
+    #[serde(default)]
+    snapshot_ids: HashMap<Principal, Vec<u8>>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -61,7 +76,64 @@ impl DataModel {
             .unwrap_or(false)
     }
 
+    pub(crate) fn set_announcer_principals(&mut self, principals: Vec<Principal>) {
+        self.announcer_principals.clear();
+        self.announcer_principals.extend(principals);
+    }
+
+    pub(crate) fn get_announcer_principals(&self) -> &HashSet<Principal> {
+        &self.announcer_principals
+    }
+
+    /// Unlike service principals, nobody can announce while no announcer is set.
+    pub(crate) fn is_announcer(&self, principal: &Principal) -> bool {
+        self.announcer_principals.contains(principal)
+    }
+
+    pub(crate) fn announce_wasm_hash(&mut self, canister_id: Principal, wasm_hash: WasmHash) {
+        self.announced_wasm_hashes.entry(canister_id).or_default().insert(wasm_hash);
+    }
+
+    pub(crate) fn is_wasm_hash_announced(&self, canister_id: &Principal, wasm_hash: &WasmHash) -> bool {
+        self.announced_wasm_hashes
+            .get(canister_id)
+            .map(|hashes| hashes.contains(wasm_hash))
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn set_snapshot_id(&mut self, canister_id: Principal, snapshot_id: Vec<u8>) {
+        self.snapshot_ids.insert(canister_id, snapshot_id);
+    }
+
+    pub(crate) fn get_snapshot_id(&self, canister_id: &Principal) -> Option<&Vec<u8>> {
+        self.snapshot_ids.get(canister_id)
+    }
+
+    pub(crate) fn remove_snapshot_id(&mut self, canister_id: &Principal) {
+        self.snapshot_ids.remove(canister_id);
+    }
+
+    pub(crate) fn get_uploaded_wasm_module(&self) -> Option<&Vec<u8>> {
+        self.current_operation.as_ref().map(|operation| &operation.wasm_module)
+    }
+
     pub(crate) fn get_wasm_module(&mut self) -> &mut Vec<u8> {
         self.current_operation.as_mut().unwrap().wasm_module.as_mut()
     }
+
+    pub(crate) fn get_wasm_registry(&self) -> &WasmRegistry {
+        &self.wasm_registry
+    }
+
+    pub(crate) fn get_wasm_registry_mut(&mut self) -> &mut WasmRegistry {
+        &mut self.wasm_registry
+    }
+
+    pub(crate) fn get_rollout_storage(&self) -> &RolloutStorage {
+        &self.rollout_storage
+    }
+
+    pub(crate) fn get_rollout_storage_mut(&mut self) -> &mut RolloutStorage {
+        &mut self.rollout_storage
+    }
 }
@@ -0,0 +1,238 @@
+use candid::Principal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uploader_canister::types::{Rollout, RolloutId, RolloutState, RolloutTargetState, WasmHash};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct RolloutStorage {
+    rollout_id_sequence: RolloutId,
+    rollouts: BTreeMap<RolloutId, Rollout>,
+}
+
+pub(crate) struct RolloutInstallTask {
+    pub target_index: usize,
+    pub canister_id: Principal,
+    pub arg: Vec<u8>,
+}
+
+impl RolloutStorage {
+    pub(crate) fn get_new_rollout_id(&mut self) -> RolloutId {
+        self.rollout_id_sequence += 1;
+        self.rollout_id_sequence
+    }
+
+    pub(crate) fn add_rollout(&mut self, rollout: Rollout) {
+        assert!(!self.rollouts.contains_key(&rollout.rollout_id));
+        self.rollouts.insert(rollout.rollout_id, rollout);
+    }
+
+    pub(crate) fn get_rollout(&self, rollout_id: &RolloutId) -> Option<&Rollout> {
+        self.rollouts.get(rollout_id)
+    }
+
+    pub(crate) fn get_running_rollout_with_wasm(&self, wasm_hash: &WasmHash) -> Option<RolloutId> {
+        self.rollouts
+            .values()
+            .find(|rollout| rollout.state == RolloutState::Running && &rollout.wasm_hash == wasm_hash)
+            .map(|rollout| rollout.rollout_id)
+    }
+
+    pub(crate) fn get_rollout_mut(&mut self, rollout_id: &RolloutId) -> Option<&mut Rollout> {
+        self.rollouts.get_mut(rollout_id)
+    }
+
+    /// Marks targets installing at the moment of an upgrade as failed and returns ids of running rollouts.
+    pub(crate) fn interrupt_installing_targets(&mut self, reason: &str) -> Vec<RolloutId> {
+        self.rollouts
+            .values_mut()
+            .filter(|rollout| rollout.state == RolloutState::Running)
+            .map(|rollout| {
+                rollout
+                    .targets
+                    .iter_mut()
+                    .filter(|target| target.state == RolloutTargetState::Installing)
+                    .for_each(|target| {
+                        target.state = RolloutTargetState::Failed {
+                            reason: reason.to_owned(),
+                        }
+                    });
+                rollout.rollout_id
+            })
+            .collect()
+    }
+
+    pub(crate) fn set_target_state(&mut self, rollout_id: &RolloutId, target_index: usize, state: RolloutTargetState) {
+        if let Some(target) = self
+            .rollouts
+            .get_mut(rollout_id)
+            .and_then(|rollout| rollout.targets.get_mut(target_index))
+        {
+            target.state = state;
+        }
+    }
+
+    /// Moves the rollout forward: updates its state and marks the targets that should be installed now.
+    pub(crate) fn take_next_install_tasks(&mut self, rollout_id: &RolloutId) -> Vec<RolloutInstallTask> {
+        let Some(rollout) = self.rollouts.get_mut(rollout_id) else {
+            return vec![];
+        };
+
+        if rollout.state != RolloutState::Running {
+            return vec![];
+        }
+
+        let canary_count = (rollout.policy.canary_count as usize).min(rollout.targets.len());
+        let canary_targets = &rollout.targets[..canary_count];
+
+        if let Some(failed) = rollout
+            .targets
+            .iter()
+            .enumerate()
+            .find(|(index, target)| {
+                matches!(target.state, RolloutTargetState::Failed { .. })
+                    && (rollout.policy.halt_on_failure || *index < canary_count)
+            })
+            .map(|(_, target)| target)
+        {
+            rollout.state = RolloutState::Halted {
+                reason: format!("install failed on canister '{}'", failed.target.canister_id),
+            };
+            return vec![];
+        }
+
+        let canary_finished = canary_targets
+            .iter()
+            .all(|target| target.state == RolloutTargetState::Installed);
+        let eligible_count = if canary_finished { rollout.targets.len() } else { canary_count };
+
+        let installing = rollout
+            .targets
+            .iter()
+            .filter(|target| target.state == RolloutTargetState::Installing)
+            .count();
+        let free_slots = (rollout.policy.concurrency as usize).saturating_sub(installing);
+
+        let shared_arg = &rollout.shared_arg;
+        let tasks: Vec<RolloutInstallTask> = rollout.targets[..eligible_count]
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, target)| target.state == RolloutTargetState::Pending)
+            .take(free_slots)
+            .map(|(target_index, target)| {
+                target.state = RolloutTargetState::Installing;
+                RolloutInstallTask {
+                    target_index,
+                    canister_id: target.target.canister_id,
+                    arg: target.target.arg.clone().unwrap_or_else(|| shared_arg.clone()),
+                }
+            })
+            .collect();
+
+        let unfinished = rollout
+            .targets
+            .iter()
+            .any(|target| target.state == RolloutTargetState::Pending || target.state == RolloutTargetState::Installing);
+        if !unfinished {
+            rollout.state = RolloutState::Completed;
+        }
+
+        tasks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::rollout::RolloutStorage;
+    use candid::Principal;
+    use uploader_canister::types::{
+        OperationType, Rollout, RolloutPolicy, RolloutState, RolloutTarget, RolloutTargetState, RolloutTargetStatus,
+    };
+
+    fn new_storage(targets: u8, concurrency: u32, canary_count: u32, halt_on_failure: bool) -> RolloutStorage {
+        let mut storage = RolloutStorage::default();
+        let rollout_id = storage.get_new_rollout_id();
+        storage.add_rollout(Rollout {
+            rollout_id,
+            initiator: Principal::anonymous(),
+            wasm_hash: "hash".to_owned(),
+            operation_type: OperationType::UpgradeCode,
+            shared_arg: vec![1],
+            policy: RolloutPolicy {
+                concurrency,
+                canary_count,
+                halt_on_failure,
+            },
+            state: RolloutState::Running,
+            targets: (0..targets)
+                .map(|index| RolloutTargetStatus {
+                    target: RolloutTarget {
+                        canister_id: Principal::from_slice(&[index]),
+                        arg: None,
+                    },
+                    state: RolloutTargetState::Pending,
+                })
+                .collect(),
+        });
+        storage
+    }
+
+    #[test]
+    fn test_canary_runs_first() {
+        let mut storage = new_storage(4, 3, 1, false);
+
+        let tasks = storage.take_next_install_tasks(&1);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].target_index, 0);
+        assert_eq!(tasks[0].arg, vec![1]);
+        assert!(storage.take_next_install_tasks(&1).is_empty());
+
+        storage.set_target_state(&1, 0, RolloutTargetState::Installed);
+        let tasks = storage.take_next_install_tasks(&1);
+        assert_eq!(tasks.iter().map(|task| task.target_index).collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        for index in 1..4 {
+            storage.set_target_state(&1, index, RolloutTargetState::Installed);
+        }
+        assert!(storage.take_next_install_tasks(&1).is_empty());
+        assert_eq!(storage.get_rollout(&1).unwrap().state, RolloutState::Completed);
+    }
+
+    #[test]
+    fn test_halt_on_failure() {
+        let mut storage = new_storage(3, 1, 0, true);
+
+        storage.take_next_install_tasks(&1);
+        storage.set_target_state(
+            &1,
+            0,
+            RolloutTargetState::Failed {
+                reason: "trap".to_owned(),
+            },
+        );
+
+        assert!(storage.take_next_install_tasks(&1).is_empty());
+        assert!(matches!(storage.get_rollout(&1).unwrap().state, RolloutState::Halted { .. }));
+    }
+
+    #[test]
+    fn test_continue_on_failure() {
+        let mut storage = new_storage(2, 1, 0, false);
+
+        storage.take_next_install_tasks(&1);
+        storage.set_target_state(
+            &1,
+            0,
+            RolloutTargetState::Failed {
+                reason: "trap".to_owned(),
+            },
+        );
+
+        let tasks = storage.take_next_install_tasks(&1);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].target_index, 1);
+
+        storage.set_target_state(&1, 1, RolloutTargetState::Installed);
+        storage.take_next_install_tasks(&1);
+        assert_eq!(storage.get_rollout(&1).unwrap().state, RolloutState::Completed);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::time::TimestampMillis;
+use candid::Principal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use uploader_canister::types::{WasmHash, WasmModuleInfo};
+
+/// The registry is kept in heap state, so its size is capped to be serialized within upgrade limits.
+pub(crate) const MAX_REGISTRY_SIZE: usize = 400_000_000;
+pub(crate) const MAX_REGISTRY_MODULES: usize = 50;
+/// An upload without new chunks for a day is considered abandoned.
+const UPLOAD_EXPIRATION_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct WasmRegistry {
+    modules: BTreeMap<WasmHash, RegisteredWasmModule>,
+    uploads: HashMap<Principal, Vec<u8>>,
+    #[serde(default)]
+    upload_times: HashMap<Principal, TimestampMillis>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RegisteredWasmModule {
+    info: WasmModuleInfo,
+    wasm_module: Vec<u8>,
+    #[serde(default)]
+    candid_service: Option<String>,
+}
+
+impl WasmRegistry {
+    pub(crate) fn get_upload_mut(&mut self, principal: &Principal, time: TimestampMillis) -> &mut Vec<u8> {
+        self.upload_times.insert(*principal, time);
+        self.uploads.entry(*principal).or_default()
+    }
+
+    pub(crate) fn get_upload_length(&self, principal: &Principal) -> usize {
+        self.uploads.get(principal).map(|upload| upload.len()).unwrap_or(0)
+    }
+
+    pub(crate) fn get_upload(&self, principal: &Principal) -> Option<&Vec<u8>> {
+        self.uploads.get(principal).filter(|upload| !upload.is_empty())
+    }
+
+    pub(crate) fn take_upload(&mut self, principal: &Principal) -> Option<Vec<u8>> {
+        self.upload_times.remove(principal);
+        self.uploads.remove(principal).filter(|upload| !upload.is_empty())
+    }
+
+    /// Uploads restored without a time are removed as well.
+    pub(crate) fn remove_expired_uploads(&mut self, time: TimestampMillis) {
+        let upload_times = &self.upload_times;
+        self.uploads.retain(|principal, _| {
+            upload_times
+                .get(principal)
+                .is_some_and(|updated| time.saturating_sub(*updated) < UPLOAD_EXPIRATION_MILLIS)
+        });
+
+        let uploads = &self.uploads;
+        self.upload_times.retain(|principal, _| uploads.contains_key(principal));
+    }
+
+    /// The total length of registered modules and uploads.
+    pub(crate) fn get_size(&self) -> usize {
+        let modules_size: usize = self.modules.values().map(|module| module.wasm_module.len()).sum();
+        let uploads_size: usize = self.uploads.values().map(|upload| upload.len()).sum();
+        modules_size + uploads_size
+    }
+
+    pub(crate) fn get_modules_count(&self) -> usize {
+        self.modules.len()
+    }
+
+    pub(crate) fn add_module(&mut self, info: WasmModuleInfo, wasm_module: Vec<u8>, candid_service: Option<String>) {
+        assert!(!self.modules.contains_key(&info.wasm_hash));
+        self.modules.insert(
+            info.wasm_hash.clone(),
+            RegisteredWasmModule {
+                info,
+                wasm_module,
+                candid_service,
+            },
+        );
+    }
+
+    pub(crate) fn remove_module(&mut self, wasm_hash: &WasmHash) -> Option<WasmModuleInfo> {
+        self.modules.remove(wasm_hash).map(|module| module.info)
+    }
+
+    pub(crate) fn contains_module(&self, wasm_hash: &WasmHash) -> bool {
+        self.modules.contains_key(wasm_hash)
+    }
+
+    pub(crate) fn get_wasm_module(&self, wasm_hash: &WasmHash) -> Option<&Vec<u8>> {
+        self.modules.get(wasm_hash).map(|module| &module.wasm_module)
+    }
+
+    pub(crate) fn get_candid_service(&self, wasm_hash: &WasmHash) -> Option<&String> {
+        self.modules.get(wasm_hash).and_then(|module| module.candid_service.as_ref())
+    }
+
+    pub(crate) fn get_modules_info(&self) -> Vec<WasmModuleInfo> {
+        self.modules.values().map(|module| module.info.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::wasm_registry::{WasmRegistry, UPLOAD_EXPIRATION_MILLIS};
+    use candid::Principal;
+    use uploader_canister::types::{WasmModuleInfo, WasmModuleMetadata};
+
+    #[test]
+    fn test_remove_expired_uploads() {
+        let mut registry = WasmRegistry::default();
+        let abandoned = Principal::from_slice(&[1]);
+        let active = Principal::from_slice(&[2]);
+
+        registry.get_upload_mut(&abandoned, 0).extend([1, 2, 3]);
+        registry.get_upload_mut(&active, 0).extend([1, 2]);
+        registry.get_upload_mut(&active, UPLOAD_EXPIRATION_MILLIS).extend([3]);
+        assert_eq!(registry.get_size(), 6);
+
+        registry.remove_expired_uploads(UPLOAD_EXPIRATION_MILLIS);
+        assert!(registry.get_upload(&abandoned).is_none());
+        assert_eq!(registry.get_upload(&active), Some(&vec![1, 2, 3]));
+        assert_eq!(registry.get_size(), 3);
+    }
+
+    #[test]
+    fn test_registry_size_counts_modules() {
+        let mut registry = WasmRegistry::default();
+        let uploader = Principal::from_slice(&[1]);
+
+        registry.get_upload_mut(&uploader, 0).extend([1, 2, 3]);
+        let wasm_module = registry.take_upload(&uploader).unwrap();
+        registry.add_module(
+            WasmModuleInfo {
+                wasm_hash: "hash".to_owned(),
+                wasm_length: wasm_module.len(),
+                metadata: WasmModuleMetadata {
+                    name: "module".to_owned(),
+                    version: None,
+                    git_commit: None,
+                },
+                uploaded_by: uploader,
+            },
+            wasm_module,
+            None,
+        );
+
+        assert_eq!(registry.get_size(), 3);
+        assert_eq!(registry.get_modules_count(), 1);
+
+        registry.remove_module(&"hash".to_owned());
+        assert_eq!(registry.get_size(), 0);
+    }
+}
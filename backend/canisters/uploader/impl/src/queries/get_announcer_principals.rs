@@ -0,0 +1,12 @@
+use crate::read_state;
+use ic_cdk_macros::query;
+use uploader_canister::get_announcer_principals::*;
+
+#[query]
+fn get_announcer_principals(_args: Args) -> Response {
+    read_state(|state| {
+        Response::Ok(GetAnnouncerPrincipalsResult {
+            announcer_principals: state.model.get_announcer_principals().iter().copied().collect(),
+        })
+    })
+}
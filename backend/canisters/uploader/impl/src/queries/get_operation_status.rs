@@ -0,0 +1,23 @@
+use crate::read_state;
+use crate::updates::perform_operation::get_module_hash;
+use ic_cdk_macros::query;
+use uploader_canister::get_operation_status::*;
+
+#[query]
+fn get_operation_status(_args: Args) -> Response {
+    read_state(|state| {
+        let uploaded_wasm = state
+            .model
+            .get_uploaded_wasm_module()
+            .filter(|wasm_module| !wasm_module.is_empty())
+            .map(|wasm_module| UploadedWasm {
+                wasm_length: wasm_module.len(),
+                wasm_hash: get_module_hash(wasm_module),
+            });
+
+        Response::Ok(GetOperationStatusResult {
+            grant: state.model.get_operation_grant().cloned(),
+            uploaded_wasm,
+        })
+    })
+}
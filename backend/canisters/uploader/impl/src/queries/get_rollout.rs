@@ -0,0 +1,15 @@
+use crate::read_state;
+use ic_cdk_macros::query;
+use uploader_canister::get_rollout::*;
+
+#[query]
+fn get_rollout(args: Args) -> Response {
+    read_state(
+        |state| match state.model.get_rollout_storage().get_rollout(&args.rollout_id) {
+            None => Response::Err(GetRolloutError::RolloutNotFound),
+            Some(rollout) => Response::Ok(GetRolloutResult {
+                rollout: rollout.clone(),
+            }),
+        },
+    )
+}
@@ -0,0 +1,17 @@
+use crate::read_state;
+use ic_cdk_macros::query;
+use uploader_canister::get_wasm_module_candid::*;
+
+#[query]
+fn get_wasm_module_candid(args: Args) -> Response {
+    read_state(|state| {
+        let registry = state.model.get_wasm_registry();
+        if !registry.contains_module(&args.wasm_hash) {
+            return Response::Err(GetWasmModuleCandidError::WasmNotFound);
+        }
+
+        Response::Ok(GetWasmModuleCandidResult {
+            candid_service: registry.get_candid_service(&args.wasm_hash).cloned(),
+        })
+    })
+}
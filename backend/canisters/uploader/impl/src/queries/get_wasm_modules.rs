@@ -0,0 +1,12 @@
+use crate::read_state;
+use ic_cdk_macros::query;
+use uploader_canister::get_wasm_modules::*;
+
+#[query]
+fn get_wasm_modules(_args: Args) -> Response {
+    read_state(|state| {
+        Response::Ok(GetWasmModulesResult {
+            wasm_modules: state.model.get_wasm_registry().get_modules_info(),
+        })
+    })
+}
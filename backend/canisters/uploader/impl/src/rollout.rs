@@ -0,0 +1,82 @@
+use crate::model::rollout::RolloutInstallTask;
+use crate::{log_error, log_info, management, mutate_state, read_state};
+use std::time::Duration;
+use uploader_canister::types::{RolloutId, RolloutState, RolloutTargetState};
+
+pub(crate) fn schedule_rollout_step(rollout_id: RolloutId) {
+    ic_cdk_timers::set_timer(Duration::ZERO, async move { rollout_step(rollout_id) });
+}
+
+pub(crate) fn resume_rollouts() {
+    let rollout_ids = mutate_state(|state| {
+        state
+            .model
+            .get_rollout_storage_mut()
+            .interrupt_installing_targets("install was interrupted by uploader upgrade")
+    });
+
+    for rollout_id in rollout_ids {
+        log_info!("Resume rollout '{rollout_id}'");
+        schedule_rollout_step(rollout_id);
+    }
+}
+
+fn rollout_step(rollout_id: RolloutId) {
+    let (tasks, previous_state, current_state) = mutate_state(|state| {
+        let storage = state.model.get_rollout_storage_mut();
+        let previous_state = storage.get_rollout(&rollout_id).map(|rollout| rollout.state.clone());
+        let tasks = storage.take_next_install_tasks(&rollout_id);
+        let current_state = storage.get_rollout(&rollout_id).map(|rollout| rollout.state.clone());
+        (tasks, previous_state, current_state)
+    });
+
+    if previous_state != current_state {
+        match current_state {
+            Some(RolloutState::Completed) => log_info!("Rollout '{rollout_id}' completed!"),
+            Some(RolloutState::Halted { reason }) => log_error!("Rollout '{rollout_id}' halted: {reason}"),
+            _ => {}
+        }
+    }
+
+    for task in tasks {
+        ic_cdk_timers::set_timer(Duration::ZERO, install_rollout_target(rollout_id, task));
+    }
+}
+
+async fn install_rollout_target(rollout_id: RolloutId, task: RolloutInstallTask) {
+    let operation = read_state(|state| {
+        let rollout = state.model.get_rollout_storage().get_rollout(&rollout_id)?;
+        let wasm_module = state.model.get_wasm_registry().get_wasm_module(&rollout.wasm_hash)?;
+        Some((rollout.operation_type.clone(), wasm_module.clone()))
+    });
+
+    let result = match operation {
+        Some((operation_type, wasm_module)) => {
+            management::install_canister_code(operation_type, task.canister_id, None, wasm_module, task.arg).await
+        }
+        None => Err("Wasm module is not found in registry".to_owned()),
+    };
+
+    let target_state = match result {
+        Ok(_) => {
+            log_info!("Rollout '{rollout_id}' installed canister '{}'", task.canister_id);
+            RolloutTargetState::Installed
+        }
+        Err(reason) => {
+            log_error!(
+                "Rollout '{rollout_id}' can not install canister '{}': {reason}",
+                task.canister_id
+            );
+            RolloutTargetState::Failed { reason }
+        }
+    };
+
+    mutate_state(|state| {
+        state
+            .model
+            .get_rollout_storage_mut()
+            .set_target_state(&rollout_id, task.target_index, target_state)
+    });
+
+    rollout_step(rollout_id);
+}
@@ -19,6 +19,10 @@ impl CanisterState {
         self.model.is_geek_user(&msg_caller())
     }
 
+    pub fn caller_is_announcer(&self) -> bool {
+        self.model.is_announcer(&msg_caller())
+    }
+
     pub fn caller_is_operator(&self) -> bool {
         self.model.is_operator(&msg_caller())
     }
@@ -0,0 +1,7 @@
+pub type TimestampMillis = u64;
+
+const NANOS_PER_MILLISECOND: u64 = 1_000_000;
+
+pub(crate) fn get_unix_epoch_time_millis() -> TimestampMillis {
+    ic_cdk::api::time() / NANOS_PER_MILLISECOND
+}
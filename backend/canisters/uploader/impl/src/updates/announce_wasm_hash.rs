@@ -0,0 +1,12 @@
+use crate::guards::caller_is_announcer;
+use crate::{log_info, mutate_state};
+use ic_cdk_macros::update;
+use uploader_canister::announce_wasm_hash::*;
+
+#[update(guard = "caller_is_announcer")]
+fn announce_wasm_hash(args: Args) -> Response {
+    log_info!("Announce wasm hash '{}' for canister '{}'", args.wasm_hash, args.canister_id);
+
+    mutate_state(|state| state.model.announce_wasm_hash(args.canister_id, args.wasm_hash));
+    Response::Ok
+}
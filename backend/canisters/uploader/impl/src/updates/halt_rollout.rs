@@ -0,0 +1,42 @@
+use crate::guards::caller_is_service_principal;
+use crate::{log_error, log_info, mutate_state};
+use ic_cdk::api::msg_caller;
+use ic_cdk_macros::update;
+use uploader_canister::halt_rollout::*;
+use uploader_canister::types::RolloutState;
+
+#[update(guard = "caller_is_service_principal")]
+fn halt_rollout(args: Args) -> Response {
+    let rollout_id = args.rollout_id;
+
+    match halt_rollout_int(args) {
+        Ok(_) => {
+            log_info!("Rollout '{rollout_id}' halted by caller");
+            Response::Ok
+        }
+        Err(error) => {
+            log_error!("Can not halt rollout '{rollout_id}': {error:?}");
+            Response::Err(error)
+        }
+    }
+}
+
+fn halt_rollout_int(args: Args) -> Result<(), HaltRolloutError> {
+    mutate_state(|state| {
+        let rollout = state
+            .model
+            .get_rollout_storage_mut()
+            .get_rollout_mut(&args.rollout_id)
+            .ok_or(HaltRolloutError::RolloutNotFound)?;
+
+        if rollout.state != RolloutState::Running {
+            return Err(HaltRolloutError::RolloutIsNotRunning);
+        }
+
+        rollout.state = RolloutState::Halted {
+            reason: format!("halted by '{}'", msg_caller()),
+        };
+
+        Ok(())
+    })
+}
@@ -1,9 +1,17 @@
+pub mod announce_wasm_hash;
 pub mod get_canister_status;
+pub mod halt_rollout;
 pub mod perform_call;
 pub mod perform_operation;
+pub mod register_wasm_module;
+pub mod remove_registry_upload;
+pub mod remove_wasm_module;
+pub mod set_announcer_principals;
 pub mod set_controllers;
 pub mod set_geek_user_principals;
 pub mod set_operation_grant;
 pub mod set_service_principals;
+pub mod start_rollout;
 pub mod update_canistergeek_information;
+pub mod upload_registry_wasm_chunk;
 pub mod upload_wasm_chunk;
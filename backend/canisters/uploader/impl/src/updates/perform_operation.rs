@@ -1,18 +1,23 @@
 use crate::guards::caller_is_operator;
-use crate::{log_error, log_info, mutate_state};
+use crate::{health_check, log_error, log_info, management, mutate_state, read_state};
+use candid::Principal;
 use ic_cdk_macros::update;
 use sha2::Digest;
 use sha2::Sha256;
 use uploader_canister::perform_operation::*;
-use uploader_canister::types::WasmProperties;
+use uploader_canister::types::{WasmProperties, WasmSource};
 
 #[update(guard = "caller_is_operator")]
 async fn perform_operation(_args: Args) -> Response {
     match perform_operation_int().await {
-        Ok(_) => {
-            log_info!("Success perform operation!");
+        Ok(None) => {
+            log_info!("Success perform operation");
             Response::Ok
         }
+        Ok(Some(snapshot_id)) => {
+            log_info!("Success perform operation, snapshot {snapshot_id:?} is kept");
+            Response::OkWithSnapshot { snapshot_id }
+        }
         Err(error) => {
             log_error!("Can not perform operation: {error:?}");
             Response::Err(error)
@@ -20,25 +25,103 @@ async fn perform_operation(_args: Args) -> Response {
     }
 }
 
-async fn perform_operation_int() -> Result<(), PerformOperationError> {
+async fn perform_operation_int() -> Result<Option<Vec<u8>>, PerformOperationError> {
     let (grant, wasm_module) = mutate_state(|state| {
-        (
-            state.model.get_operation_grant().cloned().unwrap(),
-            state.model.get_wasm_module().clone(),
-        )
+        let grant = state.model.get_operation_grant().cloned().unwrap();
+        let wasm_module = match grant.wasm_source {
+            Some(WasmSource::Registry) => state
+                .model
+                .get_wasm_registry()
+                .get_wasm_module(&grant.wasm_properties.wasm_hash)
+                .cloned()
+                .unwrap_or_default(),
+            _ => state.model.get_wasm_module().clone(),
+        };
+        (grant, wasm_module)
     });
 
     validate_perform_operation(&grant.wasm_properties, &wasm_module)?;
 
-    crate::management::install_canister_code(grant.operation_type, grant.canister_id, wasm_module, grant.arg)
-        .await
-        .map_err(|reason| PerformOperationError::OperationError { reason })?;
+    let canister_id = grant.canister_id;
+    let rollback_on_failure = grant
+        .health_check
+        .as_ref()
+        .is_some_and(|health_check| health_check.rollback_on_failure.unwrap_or(false));
+
+    let keep_snapshot = grant.take_snapshot.unwrap_or(false);
+    let snapshot_id = if keep_snapshot || rollback_on_failure {
+        let previous_snapshot_id = read_state(|state| state.model.get_snapshot_id(&canister_id).cloned());
+        let snapshot_id = management::take_snapshot(canister_id, previous_snapshot_id)
+            .await
+            .map_err(|reason| PerformOperationError::OperationError { reason })?;
+        log_info!("Taken snapshot {snapshot_id:?} of canister '{canister_id}'");
+        mutate_state(|state| state.model.set_snapshot_id(canister_id, snapshot_id.clone()));
+        Some(snapshot_id)
+    } else {
+        None
+    };
+
+    let result = management::install_canister_code(
+        grant.operation_type,
+        canister_id,
+        grant.store_canister_id,
+        wasm_module,
+        grant.arg,
+    )
+    .await;
+
+    if let Err(reason) = result {
+        return Err(match snapshot_id {
+            Some(snapshot_id) => rollback(canister_id, snapshot_id, reason).await,
+            None => PerformOperationError::OperationError { reason },
+        });
+    }
+
+    if let Some(health_check) = &grant.health_check {
+        match health_check::perform_health_check(canister_id, health_check).await {
+            Ok(response) => log_info!("Health check of canister '{canister_id}' passed: {response}"),
+            Err(response) => {
+                let rolled_back = match snapshot_id.filter(|_| rollback_on_failure) {
+                    Some(snapshot_id) => match rollback(canister_id, snapshot_id, response.clone()).await {
+                        PerformOperationError::RolledBack { .. } => true,
+                        error => {
+                            log_error!("Can not roll back canister '{canister_id}': {error:?}");
+                            false
+                        }
+                    },
+                    None => false,
+                };
+                return Err(PerformOperationError::HealthCheckFailed { response, rolled_back });
+            }
+        }
+    }
 
     mutate_state(|state| {
         state.model.set_operation_grant(None);
     });
 
-    Ok(())
+    // a snapshot taken only for the rollback is not needed after a successful operation
+    if let Some(snapshot_id) = snapshot_id.as_ref().filter(|_| !keep_snapshot) {
+        match management::delete_snapshot(canister_id, snapshot_id.clone()).await {
+            Ok(_) => mutate_state(|state| state.model.remove_snapshot_id(&canister_id)),
+            Err(error) => log_error!("Can not delete snapshot {snapshot_id:?} of canister '{canister_id}': {error}"),
+        }
+        return Ok(None);
+    }
+
+    Ok(snapshot_id)
+}
+
+async fn rollback(canister_id: Principal, snapshot_id: Vec<u8>, reason: String) -> PerformOperationError {
+    match management::load_snapshot(canister_id, snapshot_id.clone()).await {
+        Ok(_) => {
+            log_info!("Canister '{canister_id}' is restored from snapshot {snapshot_id:?}");
+            PerformOperationError::RolledBack { reason, snapshot_id }
+        }
+        Err(error) => PerformOperationError::OperationError {
+            reason: format!("{reason}; rollback failed: {error}"),
+        },
+    }
 }
 
 fn validate_perform_operation(wasm_properties: &WasmProperties, wasm_module: &Vec<u8>) -> Result<(), PerformOperationError> {
@@ -57,7 +140,7 @@ fn validate_perform_operation(wasm_properties: &WasmProperties, wasm_module: &Ve
     Ok(())
 }
 
-fn get_module_hash(module: &Vec<u8>) -> String {
+pub(crate) fn get_module_hash(module: &Vec<u8>) -> String {
     let mut hasher = Sha256::new();
     hasher.update(module);
     format!("{:x}", hasher.finalize())
@@ -0,0 +1,61 @@
+use crate::guards::caller_is_service_principal;
+use crate::model::wasm_registry::MAX_REGISTRY_MODULES;
+use crate::updates::perform_operation::get_module_hash;
+use crate::wasm_metadata::get_candid_service;
+use crate::{log_error, log_info, mutate_state};
+use ic_cdk::api::msg_caller;
+use ic_cdk_macros::update;
+use uploader_canister::register_wasm_module::*;
+use uploader_canister::types::WasmModuleInfo;
+
+#[update(guard = "caller_is_service_principal")]
+fn register_wasm_module(args: Args) -> Response {
+    match register_wasm_module_int(args) {
+        Ok(result) => {
+            log_info!("Registered wasm module: {:?}", result.wasm_module);
+            Response::Ok(result)
+        }
+        Err(error) => {
+            log_error!("Can not register wasm module: {error:?}");
+            Response::Err(error)
+        }
+    }
+}
+
+fn register_wasm_module_int(args: Args) -> Result<RegisterWasmModuleResult, RegisterWasmModuleError> {
+    let caller = msg_caller();
+
+    mutate_state(|state| {
+        let registry = state.model.get_wasm_registry_mut();
+
+        if registry.contains_module(&args.wasm_hash) {
+            return Err(RegisterWasmModuleError::AlreadyRegistered);
+        }
+
+        if registry.get_modules_count() >= MAX_REGISTRY_MODULES {
+            return Err(RegisterWasmModuleError::TooManyModules);
+        }
+
+        let upload = registry.get_upload(&caller).ok_or(RegisterWasmModuleError::NothingUploaded)?;
+
+        // the upload is kept on a wrong hash, so the caller can fix the registration arguments
+        let hash = get_module_hash(upload);
+        if hash != args.wasm_hash {
+            return Err(RegisterWasmModuleError::WrongWasmHash { hash });
+        }
+
+        let wasm_module = registry.take_upload(&caller).unwrap();
+
+        let info = WasmModuleInfo {
+            wasm_hash: hash,
+            wasm_length: wasm_module.len(),
+            metadata: args.metadata,
+            uploaded_by: caller,
+        };
+
+        let candid_service = get_candid_service(&wasm_module);
+        registry.add_module(info.clone(), wasm_module, candid_service);
+
+        Ok(RegisterWasmModuleResult { wasm_module: info })
+    })
+}
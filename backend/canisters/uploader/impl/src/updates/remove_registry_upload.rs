@@ -0,0 +1,33 @@
+use crate::guards::caller_is_service_principal;
+use crate::{log_error, log_info, mutate_state};
+use candid::Principal;
+use ic_cdk::api::msg_caller;
+use ic_cdk_macros::update;
+use uploader_canister::remove_registry_upload::*;
+
+#[update(guard = "caller_is_service_principal")]
+fn remove_registry_upload(args: Args) -> Response {
+    match remove_registry_upload_int(args) {
+        Ok(uploader) => {
+            log_info!("Removed registry upload of '{uploader}'");
+            Response::Ok
+        }
+        Err(error) => {
+            log_error!("Can not remove registry upload: {error:?}");
+            Response::Err(error)
+        }
+    }
+}
+
+fn remove_registry_upload_int(args: Args) -> Result<Principal, RemoveRegistryUploadError> {
+    let uploader = args.uploader.unwrap_or_else(msg_caller);
+
+    mutate_state(|state| {
+        state
+            .model
+            .get_wasm_registry_mut()
+            .take_upload(&uploader)
+            .map(|_| uploader)
+            .ok_or(RemoveRegistryUploadError::UploadNotFound)
+    })
+}
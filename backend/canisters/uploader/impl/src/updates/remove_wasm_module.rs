@@ -0,0 +1,37 @@
+use crate::guards::caller_is_service_principal;
+use crate::{log_error, log_info, mutate_state};
+use ic_cdk_macros::update;
+use uploader_canister::remove_wasm_module::*;
+
+#[update(guard = "caller_is_service_principal")]
+fn remove_wasm_module(args: Args) -> Response {
+    match remove_wasm_module_int(args) {
+        Ok(wasm_hash) => {
+            log_info!("Removed wasm module: {wasm_hash}");
+            Response::Ok
+        }
+        Err(error) => {
+            log_error!("Can not remove wasm module: {error:?}");
+            Response::Err(error)
+        }
+    }
+}
+
+fn remove_wasm_module_int(args: Args) -> Result<String, RemoveWasmModuleError> {
+    mutate_state(|state| {
+        if let Some(rollout_id) = state
+            .model
+            .get_rollout_storage()
+            .get_running_rollout_with_wasm(&args.wasm_hash)
+        {
+            return Err(RemoveWasmModuleError::UsedByRunningRollout { rollout_id });
+        }
+
+        state
+            .model
+            .get_wasm_registry_mut()
+            .remove_module(&args.wasm_hash)
+            .map(|info| info.wasm_hash)
+            .ok_or(RemoveWasmModuleError::WasmNotFound)
+    })
+}
@@ -0,0 +1,26 @@
+use crate::guards::caller_is_controller;
+use crate::{log_info, mutate_state};
+use candid::Principal;
+use ic_cdk_macros::update;
+use uploader_canister::set_announcer_principals::*;
+
+/// Announcers are managed by controllers, so a service principal can not vouch for its own grants.
+#[update(guard = "caller_is_controller")]
+fn set_announcer_principals(args: Args) -> Response {
+    let principals = mutate_state(|state| {
+        state.model.set_announcer_principals(args.announcer_principals);
+
+        format!(
+            "{:?}",
+            state
+                .model
+                .get_announcer_principals()
+                .iter()
+                .map(Principal::to_text)
+                .collect::<Vec<String>>()
+        )
+    });
+
+    log_info!("Set announcer principals: {principals}");
+    Response::Ok
+}
@@ -1,8 +1,9 @@
 use crate::guards::caller_is_service_principal;
-use crate::{log_error, log_info, mutate_state};
+use crate::health_check::validate_health_check;
+use crate::{log_error, log_info, mutate_state, read_state};
 use ic_cdk_macros::update;
 use uploader_canister::set_operation_grant::*;
-use uploader_canister::types::WasmProperties;
+use uploader_canister::types::{OperationGrant, WasmProperties, WasmSource};
 
 #[update(guard = "caller_is_service_principal")]
 fn set_operation_grant(args: Args) -> Response {
@@ -22,6 +23,8 @@ fn set_operation_grant_int(args: Args) -> Result<String, SetOperationGrantError>
     let grant = args.grant;
     if let Some(grant) = &grant {
         validate_wasm_properties(&grant.wasm_properties)?;
+        validate_wasm_source(grant)?;
+        validate_grant_health_check(grant)?;
     }
 
     mutate_state(|state| {
@@ -38,3 +41,26 @@ fn validate_wasm_properties(wasm_properties: &WasmProperties) -> Result<(), SetO
     }
     Ok(())
 }
+
+fn validate_wasm_source(grant: &OperationGrant) -> Result<(), SetOperationGrantError> {
+    let wasm_hash = &grant.wasm_properties.wasm_hash;
+    read_state(|state| {
+        if grant.wasm_source == Some(WasmSource::Registry) && !state.model.get_wasm_registry().contains_module(wasm_hash) {
+            return Err(SetOperationGrantError::WasmNotFoundInRegistry);
+        }
+
+        if !state.model.is_wasm_hash_announced(&grant.canister_id, wasm_hash) {
+            return Err(SetOperationGrantError::WasmHashNotAnnounced);
+        }
+        Ok(())
+    })
+}
+
+fn validate_grant_health_check(grant: &OperationGrant) -> Result<(), SetOperationGrantError> {
+    match &grant.health_check {
+        Some(health_check) => {
+            validate_health_check(health_check).map_err(|reason| SetOperationGrantError::WrongHealthCheck { reason })
+        }
+        None => Ok(()),
+    }
+}
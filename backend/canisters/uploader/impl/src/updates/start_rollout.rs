@@ -0,0 +1,106 @@
+use crate::guards::caller_is_service_principal;
+use crate::rollout::schedule_rollout_step;
+use crate::{log_error, log_info, mutate_state};
+use candid::Principal;
+use ic_cdk::api::msg_caller;
+use ic_cdk_macros::update;
+use std::collections::HashSet;
+use uploader_canister::start_rollout::*;
+use uploader_canister::types::{Rollout, RolloutPolicy, RolloutState, RolloutTarget, RolloutTargetState, RolloutTargetStatus};
+
+#[update(guard = "caller_is_service_principal")]
+fn start_rollout(args: Args) -> Response {
+    match start_rollout_int(args) {
+        Ok(result) => {
+            log_info!("Started rollout '{}'", result.rollout_id);
+            Response::Ok(result)
+        }
+        Err(error) => {
+            log_error!("Can not start rollout: {error:?}");
+            Response::Err(error)
+        }
+    }
+}
+
+fn start_rollout_int(args: Args) -> Result<StartRolloutResult, StartRolloutError> {
+    validate_targets(&args.targets)?;
+    validate_policy(&args.policy, args.targets.len())?;
+
+    let rollout_id = mutate_state(|state| {
+        if !state.model.get_wasm_registry().contains_module(&args.wasm_hash) {
+            return Err(StartRolloutError::WasmNotFoundInRegistry);
+        }
+
+        if let Some(target) = args
+            .targets
+            .iter()
+            .find(|target| !state.model.is_wasm_hash_announced(&target.canister_id, &args.wasm_hash))
+        {
+            return Err(StartRolloutError::WasmHashNotAnnounced {
+                canister_id: target.canister_id,
+            });
+        }
+
+        let storage = state.model.get_rollout_storage_mut();
+        let rollout_id = storage.get_new_rollout_id();
+
+        storage.add_rollout(Rollout {
+            rollout_id,
+            initiator: msg_caller(),
+            wasm_hash: args.wasm_hash,
+            operation_type: args.operation_type,
+            shared_arg: args.shared_arg,
+            policy: args.policy,
+            state: RolloutState::Running,
+            targets: args
+                .targets
+                .into_iter()
+                .map(|target| RolloutTargetStatus {
+                    target,
+                    state: RolloutTargetState::Pending,
+                })
+                .collect(),
+        });
+
+        Ok(rollout_id)
+    })?;
+
+    schedule_rollout_step(rollout_id);
+
+    Ok(StartRolloutResult { rollout_id })
+}
+
+fn validate_targets(targets: &[RolloutTarget]) -> Result<(), StartRolloutError> {
+    if targets.is_empty() {
+        return Err(StartRolloutError::WrongTargets {
+            reason: "targets is empty".to_owned(),
+        });
+    }
+
+    let mut canister_ids: HashSet<Principal> = HashSet::new();
+    for target in targets {
+        if !canister_ids.insert(target.canister_id) {
+            return Err(StartRolloutError::WrongTargets {
+                reason: format!("duplicate target canister '{}'", target.canister_id),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_policy(policy: &RolloutPolicy, targets_count: usize) -> Result<(), StartRolloutError> {
+    if policy.concurrency == 0 {
+        return Err(StartRolloutError::WrongPolicy {
+            reason: "concurrency must be positive".to_owned(),
+        });
+    }
+
+    if policy.canary_count as usize > targets_count {
+        return Err(StartRolloutError::WrongPolicy {
+            reason: "canary count exceeds targets count".to_owned(),
+        });
+    }
+
+    Ok(())
+}
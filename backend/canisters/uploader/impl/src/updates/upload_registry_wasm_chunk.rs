@@ -0,0 +1,63 @@
+use crate::guards::caller_is_service_principal;
+use crate::model::wasm_registry::MAX_REGISTRY_SIZE;
+use crate::time::get_unix_epoch_time_millis;
+use crate::{log_error, log_info, mutate_state, read_state};
+use ic_cdk::api::msg_caller;
+use ic_cdk_macros::update;
+use uploader_canister::upload_registry_wasm_chunk::*;
+
+#[update(guard = "caller_is_service_principal")]
+fn upload_registry_wasm_chunk(args: Args) -> Response {
+    match upload_registry_wasm_chunk_int(args) {
+        Ok(result) => {
+            log_info!("Upload registry wasm chunk: {result:?}");
+            Response::Ok(result)
+        }
+        Err(error) => {
+            log_error!("Can not put registry wasm chunk: {error:?}");
+            Response::Err(error)
+        }
+    }
+}
+
+fn upload_registry_wasm_chunk_int(args: Args) -> Result<UploadRegistryWasmChunkResult, UploadRegistryWasmChunkError> {
+    let caller = msg_caller();
+    let first = args.first;
+    let chunk = args.chunk;
+
+    validate_overflow(first, &chunk)?;
+
+    mutate_state(|state| {
+        let registry = state.model.get_wasm_registry_mut();
+        let time = get_unix_epoch_time_millis();
+        registry.remove_expired_uploads(time);
+
+        let replaced_length = if first { registry.get_upload_length(&caller) } else { 0 };
+        if registry.get_size() - replaced_length + chunk.len() > MAX_REGISTRY_SIZE {
+            return Err(UploadRegistryWasmChunkError::RegistrySizeOverflow);
+        }
+
+        let upload = registry.get_upload_mut(&caller, time);
+        if first {
+            upload.clear();
+        }
+        upload.extend(chunk);
+
+        Ok(UploadRegistryWasmChunkResult { length: upload.len() })
+    })
+}
+
+fn validate_overflow(first: bool, chunk: &[u8]) -> Result<(), UploadRegistryWasmChunkError> {
+    let current_length = read_state(|state| state.model.get_wasm_registry().get_upload_length(&msg_caller()));
+
+    let mut new_size = chunk.len();
+    if !first {
+        new_size += current_length;
+    };
+
+    if new_size > 100_000_000 {
+        return Err(UploadRegistryWasmChunkError::WasmLengthOverflow);
+    }
+
+    Ok(())
+}
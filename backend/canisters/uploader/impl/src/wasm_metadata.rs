@@ -0,0 +1,109 @@
+use flate2::read::GzDecoder;
+use std::io::Read;
+
+const WASM_MAGIC: &[u8] = b"\0asm";
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const MAX_DECOMPRESSED_LENGTH: u64 = 100_000_000;
+const CUSTOM_SECTION_ID: u8 = 0;
+const CANDID_SERVICE_SECTIONS: [&str; 2] = ["icp:public candid:service", "icp:private candid:service"];
+
+/// Returns the candid interface embedded by `ic-wasm metadata candid:service`.
+/// Gzipped modules (the usual `.wasm.gz` output of dfx) are decompressed first.
+pub(crate) fn get_candid_service(wasm_module: &[u8]) -> Option<String> {
+    if wasm_module.starts_with(GZIP_MAGIC) {
+        let wasm_module = decompress_gzip(wasm_module)?;
+        return find_candid_service(&wasm_module);
+    }
+    find_candid_service(wasm_module)
+}
+
+fn find_candid_service(wasm_module: &[u8]) -> Option<String> {
+    CANDID_SERVICE_SECTIONS
+        .iter()
+        .find_map(|name| get_custom_section(wasm_module, name))
+        .and_then(|section| String::from_utf8(section.to_vec()).ok())
+}
+
+fn decompress_gzip(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    GzDecoder::new(bytes)
+        .take(MAX_DECOMPRESSED_LENGTH)
+        .read_to_end(&mut decompressed)
+        .ok()?;
+    Some(decompressed)
+}
+
+fn get_custom_section<'a>(wasm_module: &'a [u8], name: &str) -> Option<&'a [u8]> {
+    if wasm_module.len() < 8 || &wasm_module[..4] != WASM_MAGIC {
+        return None;
+    }
+
+    let mut position = 8;
+    while position < wasm_module.len() {
+        let section_id = wasm_module[position];
+        position += 1;
+
+        let section_size = read_leb128(wasm_module, &mut position)? as usize;
+        let section_end = position.checked_add(section_size).filter(|end| *end <= wasm_module.len())?;
+
+        if section_id == CUSTOM_SECTION_ID {
+            let mut name_position = position;
+            let name_length = read_leb128(wasm_module, &mut name_position)? as usize;
+            let name_end = name_position.checked_add(name_length).filter(|end| *end <= section_end)?;
+
+            if &wasm_module[name_position..name_end] == name.as_bytes() {
+                return Some(&wasm_module[name_end..section_end]);
+            }
+        }
+
+        position = section_end;
+    }
+
+    None
+}
+
+fn read_leb128(bytes: &[u8], position: &mut usize) -> Option<u32> {
+    let mut result: u32 = 0;
+    for shift in (0..35).step_by(7) {
+        let byte = *bytes.get(*position)?;
+        *position += 1;
+        result |= ((byte & 0x7f) as u32) << shift;
+        if byte & 0x80 == 0 {
+            return Some(result);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::wasm_metadata::get_candid_service;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    fn custom_section(name: &str, content: &[u8]) -> Vec<u8> {
+        let mut payload = vec![name.len() as u8];
+        payload.extend(name.as_bytes());
+        payload.extend(content);
+
+        let mut section = vec![0, payload.len() as u8];
+        section.extend(payload);
+        section
+    }
+
+    #[test]
+    fn test_get_candid_service() {
+        let mut wasm_module = b"\0asm\x01\0\0\0".to_vec();
+        wasm_module.extend(custom_section("name", b"module"));
+        wasm_module.extend(custom_section("icp:public candid:service", b"service : {}"));
+
+        assert_eq!(get_candid_service(&wasm_module), Some("service : {}".to_owned()));
+        assert_eq!(get_candid_service(&wasm_module[..20]), None);
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&wasm_module).unwrap();
+        let gzipped_module = encoder.finish().unwrap();
+        assert_eq!(get_candid_service(&gzipped_module), Some("service : {}".to_owned()));
+    }
+}
@@ -6,8 +6,15 @@ fn main() {
 
     generate_update_candid_method!(governance_canister, add_new_proposal);
     generate_update_candid_method!(governance_canister, vote_for_proposal);
+    generate_update_candid_method!(governance_canister, delegate_vote);
     generate_update_candid_method!(governance_canister, perform_proposal);
+    generate_update_candid_method!(governance_canister, refresh_canister_did);
+    generate_update_candid_method!(governance_canister, propose_principal_rotation);
+    generate_update_candid_method!(governance_canister, confirm_principal_rotation);
+    generate_update_candid_method!(governance_canister, veto_principal_rotation);
     generate_update_candid_method!(governance_canister, set_geek_user_principals);
+    generate_update_candid_method!(governance_canister, freeze_governance);
+    generate_update_candid_method!(governance_canister, unfreeze_governance);
     generate_update_candid_method!(governance_canister, encode_candid_args);
     generate_update_candid_method!(governance_canister, decode_candid_response);
     generate_update_candid_method!(
@@ -20,9 +27,16 @@ fn main() {
 
     generate_query_candid_method!(governance_canister, get_geek_user_principals);
     generate_query_candid_method!(governance_canister, get_my_governance_participant);
+    generate_query_candid_method!(governance_canister, get_approved_module_hashes);
     generate_query_candid_method!(governance_canister, get_governance);
+    generate_query_candid_method!(governance_canister, get_governance_freeze);
+    generate_query_candid_method!(governance_canister, get_managed_canister_statuses);
+    generate_query_candid_method!(governance_canister, get_principal_rotations);
     generate_query_candid_method!(governance_canister, get_proposal);
     generate_query_candid_method!(governance_canister, get_proposals);
+    generate_query_candid_method!(governance_canister, get_upgrade_verification);
+    generate_query_candid_method!(governance_canister, get_vote_delegations);
+    generate_query_candid_method!(governance_canister, simulate_proposal);
     generate_query_candid_method!(
         governance_canister,
         get_canistergeek_information,
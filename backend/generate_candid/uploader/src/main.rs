@@ -2,14 +2,22 @@ use icgeek_candid_gen::*;
 
 #[allow(deprecated)]
 fn main() {
+    generate_update_candid_method!(uploader_canister, announce_wasm_hash);
     generate_update_candid_method!(uploader_canister, get_canister_status);
     generate_update_candid_method!(uploader_canister, set_service_principals);
+    generate_update_candid_method!(uploader_canister, set_announcer_principals);
     generate_update_candid_method!(uploader_canister, set_geek_user_principals);
     generate_update_candid_method!(uploader_canister, set_operation_grant);
     generate_update_candid_method!(uploader_canister, upload_wasm_chunk);
     generate_update_candid_method!(uploader_canister, perform_operation);
     generate_update_candid_method!(uploader_canister, perform_call);
     generate_update_candid_method!(uploader_canister, set_controllers);
+    generate_update_candid_method!(uploader_canister, upload_registry_wasm_chunk);
+    generate_update_candid_method!(uploader_canister, register_wasm_module);
+    generate_update_candid_method!(uploader_canister, remove_wasm_module);
+    generate_update_candid_method!(uploader_canister, remove_registry_upload);
+    generate_update_candid_method!(uploader_canister, start_rollout);
+    generate_update_candid_method!(uploader_canister, halt_rollout);
     generate_update_candid_method!(
         uploader_canister,
         update_canistergeek_information,
@@ -19,7 +27,12 @@ fn main() {
     );
 
     generate_query_candid_method!(uploader_canister, get_service_principals);
+    generate_query_candid_method!(uploader_canister, get_announcer_principals);
     generate_query_candid_method!(uploader_canister, get_geek_user_principals);
+    generate_query_candid_method!(uploader_canister, get_operation_status);
+    generate_query_candid_method!(uploader_canister, get_wasm_modules);
+    generate_query_candid_method!(uploader_canister, get_wasm_module_candid);
+    generate_query_candid_method!(uploader_canister, get_rollout);
     generate_query_candid_method!(
         uploader_canister,
         get_canistergeek_information,
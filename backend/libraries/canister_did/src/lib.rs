@@ -0,0 +1,45 @@
+use candid::types::{Function, Type};
+use candid::{IDLArgs, TypeEnv};
+use candid_parser::{check_prog, parse_idl_args, IDLProg};
+
+pub fn parse_canister_did(canister_did: &str) -> Result<(TypeEnv, Type), String> {
+    let ast: IDLProg = canister_did
+        .parse()
+        .map_err(|error| format!("can not parse canister did {error:?}"))?;
+
+    let mut env = TypeEnv::new();
+    let actor = check_prog(&mut env, &ast)
+        .map_err(|error| format!("can not parse canister did {error:?}"))?
+        .ok_or("can not find actor in canister did")?;
+
+    Ok((env, actor))
+}
+
+fn get_method(canister_did: &str, method: &str) -> Result<(TypeEnv, Function), String> {
+    let (env, actor) = parse_canister_did(canister_did)?;
+
+    let function = env
+        .get_method(&actor, method)
+        .map_err(|error| format!("can not find '{method}' method in actor: {error:?}"))?
+        .clone();
+
+    Ok((env, function))
+}
+
+pub fn decode_method_response(canister_did: &str, method: &str, raw: &[u8]) -> Result<IDLArgs, String> {
+    let (env, method) = get_method(canister_did, method)?;
+
+    IDLArgs::from_bytes_with_types(raw, &env, method.rets.as_slice())
+        .map_err(|error| format!("can not parse raw with types: {error:?}"))
+}
+
+/// Parses a candid text of the method response, e.g. `(variant { Ok })`, typed with the method return types,
+/// so it can be compared with a decoded response.
+pub fn parse_method_response(canister_did: &str, method: &str, response: &str) -> Result<IDLArgs, String> {
+    let (env, method) = get_method(canister_did, method)?;
+
+    parse_idl_args(response)
+        .map_err(|error| format!("can not parse response candid: {error:?}"))?
+        .annotate_types(true, &env, method.rets.as_slice())
+        .map_err(|error| format!("response candid does not match method return types: {error:?}"))
+}
@@ -30,10 +30,11 @@ header "Deploy governance canister on '${NETWORK_ALIAS}'"
 PARTICIPANT_1="record{principal \"${IDENTITY_PRINCIPAL}\"; record{name=\"identity\";proposal_permissions=vec{record{variant{UpdateGovernance};vec{variant{Add};variant{Vote};variant{Perform}}}}}}"
 PARTICIPANTS="vec{${PARTICIPANT_1}}"
 VOTING_CONFIGURATION="vec {record{variant{UpdateGovernance};record{stop_vote_count=1;positive_vote_count=1}};record{variant{CallCanister};record{stop_vote_count=1;positive_vote_count=1}};record{variant{UpgradeCanister};record{stop_vote_count=1;positive_vote_count=1}}}"
+SELF_TARGETING_VOTING_CONFIG="opt record{stop_vote_count=1;positive_vote_count=1}"
 GEEK_USER_PRINCIPALS="vec {principal \"${IDENTITY_PRINCIPAL}\"}"
 
 dfx --identity "$IDENTITY" deploy --network "${NETWORK_ALIAS}" --argument \
-"(record { governance = record{participants=${PARTICIPANTS}; voting_configuration=${VOTING_CONFIGURATION}}; geek_user_principals=${GEEK_USER_PRINCIPALS}})" governance
+"(record { governance = record{participants=${PARTICIPANTS}; voting_configuration=${VOTING_CONFIGURATION}; self_targeting_voting_config=${SELF_TARGETING_VOTING_CONFIG}}; geek_user_principals=${GEEK_USER_PRINCIPALS}})" governance
 
 
 #dfx canister call governance add_new_proposal '(record{proposal_detail=variant {UpdateGovernance = record{new_governance=record{participants=vec{record{principal "lpag6-ktxsv-3oewm-s4gok-fzo2e-qcn2v-kzdpi-eozwc-ddv2o-rbbx4-wae"; record{name="slaig";proposal_permissions=vec{record{variant{UpdateGovernance}; vec{variant{Add};variant{Vote};variant{Perform}} }}}}}; voting_configuration=vec {record{variant{UpdateGovernance};record{stop_vote_count=1;positive_vote_count=1}}}}}}})'
@@ -21,12 +21,16 @@ IDENTITY_PRINCIPAL="$(dfx --identity "$IDENTITY" identity get-principal)"
 
 PERMISSIONS_ALL="vec{record{variant{UpdateGovernance};vec{variant{Add};variant{Vote};variant{Perform}}};record{variant{CallCanister};vec{variant{Add};variant{Vote};variant{Perform}}};record{variant{UpgradeCanister};vec{variant{Add};variant{Vote};variant{Perform}}};}"
 
-PARTICIPANT="record{principal \"${PRINCIPAL}\"; record{name=\"participant\";proposal_permissions=${PERMISSIONS_ALL}}; }"
+ROLES="vec{record{name=\"admin\";proposal_permissions=${PERMISSIONS_ALL}}}"
+
+PARTICIPANT="record{principal \"${PRINCIPAL}\"; record{name=\"participant\";proposal_permissions=vec{};roles=opt vec{\"admin\"}}; }"
 PARTICIPANTS="vec{${PARTICIPANT}}"
 
 VOTING_CONFIGURATION="vec {record{variant{UpdateGovernance};record{stop_vote_count=1;positive_vote_count=1}};record{variant{CallCanister};record{stop_vote_count=1;positive_vote_count=1}};record{variant{UpgradeCanister};record{stop_vote_count=1;positive_vote_count=1}}}"
 
-NEW_GOVERNANCE="record{participants=${PARTICIPANTS}; voting_configuration=${VOTING_CONFIGURATION}}"
+SELF_TARGETING_VOTING_CONFIG="opt record{stop_vote_count=1;positive_vote_count=1}"
+
+NEW_GOVERNANCE="record{participants=${PARTICIPANTS}; voting_configuration=${VOTING_CONFIGURATION}; self_targeting_voting_config=${SELF_TARGETING_VOTING_CONFIG}; roles=opt ${ROLES}}"
 
 #1. Create proposal
 dfx canister --identity "$IDENTITY" --network "${NETWORK_ALIAS}" call governance add_new_proposal "(record{proposal_detail=variant {UpdateGovernance = record{new_governance=${NEW_GOVERNANCE}}}})"